@@ -0,0 +1,189 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bus::{GameEvent, Subscriber};
+use crate::drugs::Drug;
+use crate::events::EventType;
+use crate::locations::{Location, get_location_list};
+use crate::storage;
+
+const ACHIEVEMENTS_FILE: &str = "achievements.json";
+const DEBT_FREE_DAY: u32 = 10;
+const MILLIONAIRE_PROFIT: i64 = 1_000_000;
+const MILLIONAIRE_DAYS: u32 = 30;
+const MUGGINGS_SURVIVED: u32 = 5;
+
+// MARK: Achievement enum
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Achievement {
+  DebtFree,
+  Survivor,
+  Millionaire,
+  JustSayNo,
+  Globetrotter,
+}
+
+impl Achievement {
+  // MARK: name()
+  pub fn name(&self) -> &'static str {
+    match self {
+      Achievement::DebtFree => "Debt Free",
+      Achievement::Survivor => "Survivor",
+      Achievement::Millionaire => "Millionaire",
+      Achievement::JustSayNo => "Just Say No",
+      Achievement::Globetrotter => "Globetrotter",
+    }
+  }
+
+  // MARK: description()
+  pub fn description(&self) -> String {
+    match self {
+      Achievement::DebtFree => format!("Pay off your debt before day {}", DEBT_FREE_DAY),
+      Achievement::Survivor => format!("Survive {} muggings", MUGGINGS_SURVIVED),
+      Achievement::Millionaire => "Make $1,000,000 in a one-month game".to_string(),
+      Achievement::JustSayNo => "Finish a game without ever buying Heroin".to_string(),
+      Achievement::Globetrotter => "Visit every location in a single game".to_string(),
+    }
+  }
+
+  // MARK: goal()
+  pub fn goal(&self) -> u32 {
+    match self {
+      Achievement::Survivor => MUGGINGS_SURVIVED,
+      Achievement::Globetrotter => get_location_list().len() as u32,
+      _ => 1,
+    }
+  }
+}
+
+// MARK: get_achievement_list()
+pub fn get_achievement_list() -> [Achievement; 5] {
+  [
+    Achievement::DebtFree,
+    Achievement::Survivor,
+    Achievement::Millionaire,
+    Achievement::JustSayNo,
+    Achievement::Globetrotter,
+  ]
+}
+
+// MARK: - Progress struct
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Progress {
+  pub progress: u32,
+  pub unlocked: Option<String>,
+}
+
+// MARK: - Run struct
+/// What's happened so far in the current game, for the single-game achievements.
+#[derive(Debug, Clone)]
+struct Run {
+  day: u32,
+  bought_heroin: bool,
+  visited: HashSet<Location>,
+}
+
+impl Default for Run {
+  fn default() -> Self {
+    Self {
+      day: 0,
+      bought_heroin: false,
+      visited: HashSet::from([Location::default()]),
+    }
+  }
+}
+
+// MARK: - Achievements struct
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Achievements {
+  progress: HashMap<Achievement, Progress>,
+  #[serde(skip)]
+  run: Run,
+  #[serde(skip)]
+  unlocked: Vec<Achievement>,
+  /// Whether any progress has been made since the last save.
+  #[serde(skip)]
+  changed: bool,
+}
+
+impl Achievements {
+  // MARK: load()
+  pub fn load() -> Self {
+    storage::load(ACHIEVEMENTS_FILE)
+  }
+
+  // MARK: save()
+  pub fn save(&self) -> std::io::Result<()> {
+    storage::save(ACHIEVEMENTS_FILE, self)
+  }
+
+  // MARK: get()
+  pub fn get(&self, achievement: Achievement) -> Progress {
+    self.progress.get(&achievement).cloned().unwrap_or_default()
+  }
+
+  // MARK: take_unlocked()
+  /// Achievements unlocked since the last call.
+  pub fn take_unlocked(&mut self) -> Vec<Achievement> {
+    std::mem::take(&mut self.unlocked)
+  }
+
+  // MARK: take_changed()
+  /// Whether progress was made since the last call, and so needs saving.
+  pub fn take_changed(&mut self) -> bool {
+    std::mem::take(&mut self.changed)
+  }
+
+  // MARK: advance() [helper]
+  fn advance(&mut self, achievement: Achievement, progress: u32) {
+    let entry = self.progress.entry(achievement).or_default();
+    let before = entry.progress;
+    entry.progress = entry.progress.max(progress).min(achievement.goal());
+    self.changed |= entry.progress != before;
+    if entry.progress >= achievement.goal() && entry.unlocked.is_none() {
+      entry.unlocked = Some(storage::today());
+      self.unlocked.push(achievement);
+    }
+  }
+}
+
+// MARK: Subscriber impl
+impl Subscriber for Achievements {
+  fn notify(&mut self, event: &GameEvent) {
+    match event {
+      GameEvent::Bought {
+        drug: Drug::Heroin, ..
+      } => self.run.bought_heroin = true,
+      GameEvent::Traveled { to, .. } => {
+        self.run.day += 1;
+        self.run.visited.insert(*to);
+        self.advance(Achievement::Globetrotter, self.run.visited.len() as u32);
+      }
+      GameEvent::Waited { .. } => self.run.day += 1,
+      GameEvent::DebtRepaid { remaining, .. }
+        if remaining.is_zero() && self.run.day < DEBT_FREE_DAY =>
+      {
+        self.advance(Achievement::DebtFree, 1);
+      }
+      GameEvent::EventTriggered { event, .. } if event.e_type == EventType::Mugging => {
+        let survived = self.get(Achievement::Survivor).progress + 1;
+        self.advance(Achievement::Survivor, survived);
+      }
+      GameEvent::GameEnded {
+        profit,
+        days_played,
+        ..
+      } => {
+        if *profit >= MILLIONAIRE_PROFIT && *days_played <= MILLIONAIRE_DAYS {
+          self.advance(Achievement::Millionaire, 1);
+        }
+        if !self.run.bought_heroin {
+          self.advance(Achievement::JustSayNo, 1);
+        }
+        self.run = Run::default();
+      }
+      _ => {}
+    }
+  }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use crate::drugs::{Drug, get_drug_list, get_drug_price};
+use crate::events::EventType;
+use crate::game::Game;
+use crate::locations::{Location, get_location_list};
+use crate::money::Money;
+
+// MARK: advisor constants
+/// How much each day of age adds to the risk of acting on a remembered price.
+const STALENESS_RISK: f32 = 0.05;
+const HEAT_RISK: f32 = 0.5;
+const LOW_RISK: f32 = 0.1;
+const HIGH_RISK: f32 = 0.25;
+
+// MARK: Risk enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Risk {
+  Low,
+  Medium,
+  High,
+}
+
+// MARK: risk display
+impl std::fmt::Display for Risk {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Risk::Low => write!(f, "Low"),
+      Risk::Medium => write!(f, "Medium"),
+      Risk::High => write!(f, "High"),
+    }
+  }
+}
+
+// MARK: - Sighting struct
+/// Prices the player saw at a location, and on which day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sighting {
+  pub day: u32,
+  pub prices: [u32; 7],
+}
+
+// MARK: - Tip struct
+/// A suggested run: buy a drug here and sell it where it was last seen dearer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tip {
+  pub drug: Drug,
+  pub buy_price: u32,
+  pub sell_at: Location,
+  pub sell_price: u32,
+  /// Days since the sell price was seen.
+  pub age: u32,
+  /// How many the player can buy right now.
+  pub amount: u32,
+  pub risk: Risk,
+}
+
+impl Tip {
+  // MARK: margin()
+  /// Expected profit per unit.
+  pub fn margin(&self) -> u32 {
+    self.sell_price.saturating_sub(self.buy_price)
+  }
+
+  // MARK: margin_percent()
+  pub fn margin_percent(&self) -> u32 {
+    (self.margin() as u64 * 100 / self.buy_price.max(1) as u64) as u32
+  }
+
+  // MARK: expected_profit()
+  pub fn expected_profit(&self) -> Money {
+    Money::from(self.margin()).times(self.amount)
+  }
+}
+
+// MARK: - Advisor struct
+/// Remembers the prices seen around town and points out the best trade from here.
+#[derive(Debug, Default, Clone)]
+pub struct Advisor {
+  sightings: HashMap<Location, Sighting>,
+}
+
+impl Advisor {
+  // MARK: record()
+  pub fn record(&mut self, location: Location, day: u32, prices: [u32; 7]) {
+    self.sightings.insert(location, Sighting { day, prices });
+  }
+
+  // MARK: sighting()
+  pub fn sighting(&self, location: Location) -> Option<&Sighting> {
+    self.sightings.get(&location)
+  }
+
+  // MARK: suggest()
+  /// The buy here with the most expected profit elsewhere, if any would make money.
+  pub fn suggest(&self, game: &Game) -> Option<Tip> {
+    let mut best: Option<Tip> = None;
+    for location in get_location_list() {
+      if location == game.location {
+        continue;
+      }
+      let Some(sighting) = self.sighting(location) else {
+        continue;
+      };
+      for drug in get_drug_list() {
+        let tip = Tip {
+          drug,
+          buy_price: get_drug_price(drug, &game.prices),
+          sell_at: location,
+          sell_price: get_drug_price(drug, &sighting.prices),
+          age: game.day.saturating_sub(sighting.day),
+          amount: game.max_buy(drug),
+          risk: Risk::Low,
+        };
+        if tip.margin() == 0 {
+          continue;
+        }
+        let better = best.is_none_or(|best| {
+          (tip.expected_profit(), tip.margin()) > (best.expected_profit(), best.margin())
+        });
+        if better {
+          best = Some(Tip {
+            risk: assess_risk(game, location, tip.age),
+            ..tip
+          });
+        }
+      }
+    }
+    best
+  }
+}
+
+// MARK: assess_risk()
+/// Rough odds of a trip going wrong: the destination's police and mugging odds,
+/// the player's heat, and how stale the price is.
+fn assess_risk(game: &Game, destination: Location, age: u32) -> Risk {
+  let table = destination.event_table(game.ruleset.event_chance);
+  let total: f32 = table.weights.iter().map(|(_, weight)| weight).sum();
+  let dangerous: f32 = table
+    .weights
+    .iter()
+    .filter(|(e_type, _)| e_type.is_police() || *e_type == EventType::Mugging)
+    .map(|(_, weight)| weight)
+    .sum();
+  let danger = table.chance * dangerous / total.max(f32::EPSILON);
+  let score = danger + game.heat.level() * HEAT_RISK + age as f32 * STALENESS_RISK;
+  match score {
+    s if s < LOW_RISK => Risk::Low,
+    s if s < HIGH_RISK => Risk::Medium,
+    _ => Risk::High,
+  }
+}
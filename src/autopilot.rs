@@ -0,0 +1,168 @@
+use crate::drugs::{get_drug_list, get_drug_price};
+use crate::game::Game;
+use crate::locations::{Location, get_location_list};
+use crate::money::Money;
+use crate::replay::Action;
+
+// MARK: autopilot constants
+pub const MAX_AUTOPILOT_DAYS: u32 = 365;
+pub const MIN_AUTOPILOT_SPEED: f32 = 0.5;
+pub const MAX_AUTOPILOT_SPEED: f32 = 20.0;
+/// Share of spare cash the bot puts towards debt each day.
+const REPAY_SHARE: f32 = 0.5;
+
+// MARK: Phase enum
+/// Where the bot is in its daily routine: sell, repay, buy, then move on.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+  #[default]
+  Sell,
+  Repay,
+  Buy,
+  Travel,
+}
+
+// MARK: - Bot struct
+/// A simple scripted player that sells at a profit, pays down debt,
+/// buys on the advisor's best tip and travels to cash it in.
+#[derive(Debug, Default, Clone)]
+pub struct Bot {
+  phase: Phase,
+  target: Option<Location>,
+}
+
+impl Bot {
+  // MARK: next_action()
+  /// The bot's next move, made through the same actions a player's clicks record.
+  pub fn next_action(&mut self, game: &Game) -> Action {
+    let last_day = game.days_left.is_some_and(|days| days <= 1);
+    loop {
+      match self.phase {
+        Phase::Sell => {
+          let sale = get_drug_list().into_iter().find_map(|drug| {
+            let held = game.inventory.get_amount(drug).unwrap_or(0);
+            let price = Money::from(get_drug_price(drug, &game.prices));
+            let cost = game.inventory.get_cost(drug).unwrap_or_default();
+            (held > 0 && (price > cost || last_day)).then_some(Action::Sell(drug, held))
+          });
+          match sale {
+            Some(action) => return action,
+            None => self.phase = Phase::Repay,
+          }
+        }
+        Phase::Repay => {
+          self.phase = Phase::Buy;
+          let share = if last_day { 1.0 } else { REPAY_SHARE };
+          let amount = game.debt.min(game.cash.scale(share));
+          if !amount.is_zero() {
+            return Action::RepayDebt(amount);
+          }
+        }
+        Phase::Buy => match game.advisor.suggest(game) {
+          Some(tip) if tip.amount > 0 && !last_day => {
+            self.target = Some(tip.sell_at);
+            return Action::Buy(tip.drug, tip.amount);
+          }
+          _ => self.phase = Phase::Travel,
+        },
+        Phase::Travel => {
+          self.skip();
+          let destination = match self.target.take() {
+            Some(target) if game.inventory.has_items() => target,
+            _ => stalest_location(game),
+          };
+          return Action::Travel(destination);
+        }
+      }
+    }
+  }
+
+  // MARK: skip()
+  /// Moves on from the current phase, e.g. after a trade in it fails.
+  pub fn skip(&mut self) {
+    self.phase = match self.phase {
+      Phase::Sell => Phase::Repay,
+      Phase::Repay => Phase::Buy,
+      Phase::Buy => Phase::Travel,
+      Phase::Travel => Phase::Sell,
+    };
+  }
+}
+
+// MARK: stalest_location()
+/// The other location whose prices the bot has the oldest news of, or none at all.
+fn stalest_location(game: &Game) -> Location {
+  get_location_list()
+    .into_iter()
+    .filter(|location| *location != game.location)
+    .min_by_key(|location| game.advisor.sighting(*location).map(|s| s.day))
+    .unwrap_or_default()
+}
+
+// MARK: - Autopilot struct
+/// Hands the game to the bot for a number of days, one action at a time.
+#[derive(Debug, Clone)]
+pub struct Autopilot {
+  pub days: u32,
+  /// Actions per second.
+  pub speed: f32,
+  days_left: u32,
+  next_step_at: f64,
+  bot: Bot,
+}
+
+impl Default for Autopilot {
+  fn default() -> Self {
+    Self {
+      days: 7,
+      speed: 4.0,
+      days_left: 0,
+      next_step_at: 0.0,
+      bot: Bot::default(),
+    }
+  }
+}
+
+impl Autopilot {
+  // MARK: is_running()
+  pub fn is_running(&self) -> bool {
+    self.days_left > 0
+  }
+
+  // MARK: days_left()
+  pub fn days_left(&self) -> u32 {
+    self.days_left
+  }
+
+  // MARK: start()
+  pub fn start(&mut self) {
+    self.days_left = self.days;
+    self.next_step_at = 0.0;
+    self.bot = Bot::default();
+  }
+
+  // MARK: stop()
+  pub fn stop(&mut self) {
+    self.days_left = 0;
+  }
+
+  // MARK: tick()
+  /// Takes the bot's next action if it's due at `now`, in seconds.
+  pub fn tick(&mut self, game: &mut Game, now: f64) {
+    if !self.is_running() || now < self.next_step_at {
+      return;
+    }
+    if game.game_over {
+      self.stop();
+      return;
+    }
+    self.next_step_at = now + 1.0 / self.speed.max(MIN_AUTOPILOT_SPEED) as f64;
+    let action = self.bot.next_action(game);
+    if action.apply(game).is_err() {
+      self.bot.skip();
+    }
+    if matches!(action, Action::Travel(_) | Action::Wait) {
+      self.days_left -= 1;
+    }
+  }
+}
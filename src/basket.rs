@@ -0,0 +1,179 @@
+use std::fmt;
+
+use crate::drugs::{Drug, get_drug_list, get_drug_price};
+use crate::game::Game;
+use crate::money::Money;
+use crate::trade::TradeError;
+
+// MARK: Order enum
+/// A trade staged in the basket, waiting to be executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+  Buy(Drug, u32),
+  Sell(Drug, u32),
+}
+
+impl Order {
+  fn drug(&self) -> Drug {
+    match self {
+      Order::Buy(drug, _) | Order::Sell(drug, _) => *drug,
+    }
+  }
+}
+
+// MARK: Order display
+impl fmt::Display for Order {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Order::Buy(drug, amount) => write!(f, "Buy {} {}", amount, drug),
+      Order::Sell(drug, amount) => write!(f, "Sell {} {}", amount, drug),
+    }
+  }
+}
+
+// MARK: - BasketPreview struct
+/// Where the player would stand if the basket went through at today's prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BasketPreview {
+  pub cash: Money,
+  pub holdings: [u32; 7],
+  pub free_space: u32,
+}
+
+// MARK: - Basket struct
+/// Orders staged to go through together, all or nothing.
+///
+/// Sells always run before buys, so their proceeds and freed space pay for the buys.
+#[derive(Debug, Default, Clone)]
+pub struct Basket {
+  orders: Vec<Order>,
+}
+
+impl Basket {
+  // MARK: add()
+  /// Stages an order, folding it into any existing order for the same drug and side.
+  pub fn add(&mut self, order: Order) {
+    let existing = self
+      .orders
+      .iter_mut()
+      .find_map(|staged| match (staged, order) {
+        (Order::Buy(drug, amount), Order::Buy(new_drug, extra))
+        | (Order::Sell(drug, amount), Order::Sell(new_drug, extra))
+          if *drug == new_drug =>
+        {
+          Some((amount, extra))
+        }
+        _ => None,
+      });
+    match existing {
+      Some((amount, extra)) => *amount = amount.saturating_add(extra),
+      None => self.orders.push(order),
+    }
+  }
+
+  // MARK: remove()
+  pub fn remove(&mut self, order: Order) {
+    self.orders.retain(|staged| *staged != order);
+  }
+
+  // MARK: clear()
+  pub fn clear(&mut self) {
+    self.orders.clear();
+  }
+
+  // MARK: is_empty()
+  pub fn is_empty(&self) -> bool {
+    self.orders.is_empty()
+  }
+
+  // MARK: orders()
+  /// The staged orders, in the order they'll be executed.
+  pub fn orders(&self) -> Vec<Order> {
+    let (sells, buys): (Vec<Order>, Vec<Order>) = self
+      .orders
+      .iter()
+      .partition(|order| matches!(order, Order::Sell(..)));
+    sells.into_iter().chain(buys).collect()
+  }
+
+  // MARK: preview()
+  /// Checks every order against the game as it stands, without touching it.
+  pub fn preview(&self, game: &Game) -> Result<BasketPreview, TradeError> {
+    let mut preview = BasketPreview {
+      cash: game.cash,
+      holdings: get_drug_list().map(|drug| game.inventory.get_amount(drug).unwrap_or(0)),
+      free_space: game.free_space(),
+    };
+    for order in self.orders() {
+      let price = Money::from(get_drug_price(order.drug(), &game.prices));
+      let held = &mut preview.holdings[order.drug() as usize];
+      match order {
+        Order::Buy(_, 0) | Order::Sell(_, 0) => return Err(TradeError::InvalidAmount),
+        Order::Buy(_, amount) => {
+          if amount > preview.free_space {
+            return Err(TradeError::InsufficientSpace);
+          }
+          preview.cash = preview
+            .cash
+            .checked_sub(price.times(amount))
+            .ok_or(TradeError::InsufficientCash)?;
+          preview.free_space -= amount;
+          *held += amount;
+        }
+        Order::Sell(_, amount) => {
+          *held = held
+            .checked_sub(amount)
+            .ok_or(TradeError::InsufficientStock)?;
+          preview.cash += price.times(amount);
+          preview.free_space += amount;
+        }
+      }
+    }
+    Ok(preview)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn started() -> Game {
+    let mut game = Game::with_seed(5);
+    game.start();
+    game.buy(Drug::Weed, 10).unwrap();
+    game
+  }
+
+  #[test]
+  fn a_failing_basket_changes_nothing() {
+    let mut game = started();
+    let holdings = get_drug_list().map(|drug| game.inventory.holding(drug));
+    let (cash, actions, undo) = (game.cash, game.actions.len(), game.undo.len());
+    game.basket.add(Order::Sell(Drug::Weed, 5));
+    // the sale frees 5 units of space, which still leaves the buy one short
+    game
+      .basket
+      .add(Order::Buy(Drug::Weed, game.free_space() + 6));
+    assert_eq!(game.execute_basket(), Err(TradeError::InsufficientSpace));
+    assert_eq!(game.cash, cash);
+    assert_eq!(
+      get_drug_list().map(|drug| game.inventory.holding(drug)),
+      holdings
+    );
+    assert_eq!((game.actions.len(), game.undo.len()), (actions, undo));
+    assert!(!game.basket.is_empty(), "the basket is kept to fix up");
+  }
+
+  #[test]
+  fn an_executed_basket_undoes_as_one() {
+    let mut game = started();
+    let (cash, weed) = (game.cash, game.inventory.holding(Drug::Weed));
+    game.basket.add(Order::Sell(Drug::Weed, 4));
+    game.basket.add(Order::Buy(Drug::Shrooms, 1));
+    assert_eq!(game.execute_basket().map(|receipts| receipts.len()), Ok(2));
+    assert_eq!(game.undo().map(|receipts| receipts.len()), Some(2));
+    assert_eq!(game.cash, cash);
+    assert_eq!(game.inventory.holding(Drug::Weed), weed);
+    assert_eq!(game.inventory.holding(Drug::Shrooms), None);
+  }
+}
@@ -0,0 +1,158 @@
+use crate::drugs::Drug;
+use crate::events::Event;
+use crate::locations::Location;
+use crate::money::Money;
+use crate::trade::TradeReceipt;
+
+// MARK: GameEvent enum
+/// Something that happened in the engine, published for anyone who needs to react to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+  Bought {
+    drug: Drug,
+    amount: u32,
+    price: Money,
+  },
+  Sold {
+    drug: Drug,
+    amount: u32,
+    price: Money,
+    cost: Money,
+  },
+  Traveled {
+    from: Location,
+    to: Location,
+  },
+  Waited {
+    location: Location,
+  },
+  InterestCharged {
+    amount: Money,
+  },
+  DebtRepaid {
+    amount: Money,
+    remaining: Money,
+  },
+  TradeUndone {
+    receipt: TradeReceipt,
+  },
+  EventTriggered {
+    event: Event,
+    lost: Money,
+  },
+  GameEnded {
+    score: i64,
+    /// Final net worth less the starting one.
+    profit: i64,
+    days_played: u32,
+  },
+}
+
+// MARK: - Subscriber trait
+/// Anything that reacts to events as they happen, e.g. a log, sounds or network sync.
+pub trait Subscriber {
+  fn notify(&mut self, event: &GameEvent);
+}
+
+// MARK: - EventBus struct
+/// Hands published events to every registered subscriber, and queues them
+/// for a frontend that would rather drain them once a frame.
+///
+/// Nothing is queued until a frontend calls `listen()`, so headless callers
+/// that never drain it don't pile up events.
+#[derive(Default)]
+pub struct EventBus {
+  subscribers: Vec<Box<dyn Subscriber>>,
+  pending: Vec<GameEvent>,
+  listening: bool,
+}
+
+// MARK: EventBus clone
+/// A copy of the bus starts with no subscribers, so trying something out on
+/// a copy of the game, e.g. a basket, doesn't announce it.
+impl Clone for EventBus {
+  fn clone(&self) -> Self {
+    Self {
+      subscribers: Vec::new(),
+      pending: self.pending.clone(),
+      listening: self.listening,
+    }
+  }
+}
+
+impl EventBus {
+  // MARK: subscribe()
+  pub fn subscribe(&mut self, subscriber: Box<dyn Subscriber>) {
+    self.subscribers.push(subscriber);
+  }
+
+  // MARK: listen()
+  pub fn listen(&mut self) {
+    self.listening = true;
+  }
+
+  // MARK: is_listening()
+  pub fn is_listening(&self) -> bool {
+    self.listening
+  }
+
+  // MARK: publish()
+  pub fn publish(&mut self, event: GameEvent) {
+    for subscriber in self.subscribers.iter_mut() {
+      subscriber.notify(&event);
+    }
+    if self.listening {
+      self.pending.push(event);
+    }
+  }
+
+  // MARK: drain()
+  pub fn drain(&mut self) -> Vec<GameEvent> {
+    std::mem::take(&mut self.pending)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cell::RefCell;
+  use std::rc::Rc;
+
+  use super::*;
+  use crate::game::Game;
+
+  struct Recorder(Rc<RefCell<Vec<GameEvent>>>);
+
+  impl Subscriber for Recorder {
+    fn notify(&mut self, event: &GameEvent) {
+      self.0.borrow_mut().push(event.clone());
+    }
+  }
+
+  #[test]
+  fn subscribers_hear_every_event() {
+    let heard = Rc::new(RefCell::new(Vec::new()));
+    let mut game = Game::with_seed(1);
+    game.bus.subscribe(Box::new(Recorder(heard.clone())));
+    game.start();
+    game.buy(Drug::Weed, 1).unwrap();
+    game.wait();
+    let heard = heard.borrow();
+    assert!(matches!(
+      heard[0],
+      GameEvent::Bought {
+        drug: Drug::Weed,
+        amount: 1,
+        ..
+      }
+    ));
+    assert!(
+      heard
+        .iter()
+        .any(|event| matches!(event, GameEvent::Waited { .. }))
+    );
+    assert!(
+      game.bus.drain().is_empty(),
+      "nothing queues without a listener"
+    );
+  }
+}
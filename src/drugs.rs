@@ -1,7 +1,10 @@
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+pub type PriceRanges = [(u32, u32); 7];
 
 // MARK: price range constants
-pub const PRICE_RANGES: [(u32, u32); 7] = [
+pub const PRICE_RANGES: PriceRanges = [
   (40, 80),   // Weed
   (50, 90),   // Cocaine
   (80, 120),  // Meth
@@ -12,7 +15,7 @@ pub const PRICE_RANGES: [(u32, u32); 7] = [
 ];
 
 // MARK: drug enum
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Drug {
   #[default]
   Weed,
@@ -60,19 +63,17 @@ pub fn get_drug_price(drug: Drug, prices: &[u32; 7]) -> u32 {
 }
 
 // MARK: get_rand_drug()
-pub fn get_rand_drug() -> Drug {
-  let mut rng = rand::rng();
+pub fn get_rand_drug(rng: &mut impl Rng) -> Drug {
   let drugs = get_drug_list();
   let rand_index = rng.random_range(0..drugs.len());
   drugs[rand_index]
 }
 
 // MARK: get_rand_prices()
-pub fn get_rand_prices() -> [u32; 7] {
-  let mut rng = rand::rng();
+pub fn get_rand_prices(price_ranges: &PriceRanges, rng: &mut impl Rng) -> [u32; 7] {
   let mut prices = [0; 7];
 
-  for (i, &(min, max)) in PRICE_RANGES.iter().enumerate() {
+  for (i, &(min, max)) in price_ranges.iter().enumerate() {
     prices[i] = rng.random_range(min..=max);
   }
 
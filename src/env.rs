@@ -0,0 +1,156 @@
+use crate::bus::Subscriber;
+use crate::drugs::get_drug_list;
+use crate::events::EventType;
+use crate::game::{Game, GameLength};
+use crate::locations::get_location_list;
+use crate::networth::Valuation;
+use crate::ruleset::Ruleset;
+
+// MARK: env constants
+/// Prices, holdings, cash, debt, days left, free space, heat,
+/// the location one-hot and the event type one-hot.
+pub const OBSERVATION_SIZE: usize = 7 + 7 + 5 + 6 + 7;
+/// Wait, travel to each location, buy max and sell all of each drug,
+/// repay as much as possible, and accept an equipment offer.
+pub const ACTION_COUNT: usize = 1 + 6 + 7 + 7 + 1 + 1;
+
+const TRAVEL: usize = 1;
+const BUY_MAX: usize = TRAVEL + 6;
+const SELL_ALL: usize = BUY_MAX + 7;
+const REPAY: usize = SELL_ALL + 7;
+const ACCEPT_OFFER: usize = REPAY + 1;
+
+/// The game as numbers, laid out as described on `OBSERVATION_SIZE`.
+/// Days left is -1 in an endless game.
+pub type Observation = [f32; OBSERVATION_SIZE];
+
+// MARK: - Env struct
+/// The engine as a reinforcement learning environment, with a discrete action space.
+///
+/// Rewards are the change in net worth at current prices, so an episode's rewards
+/// add up to its final net worth less the starting one.
+pub struct Env {
+  length: GameLength,
+  ruleset: Ruleset,
+  game: Game,
+  worth: i64,
+}
+
+impl Env {
+  // MARK: Env::new()
+  pub fn new(length: GameLength, ruleset: Ruleset) -> Self {
+    let mut env = Self {
+      length,
+      ruleset,
+      game: Game::with_seed(0),
+      worth: 0,
+    };
+    env.reset(0);
+    env
+  }
+
+  // MARK: game()
+  pub fn game(&self) -> &Game {
+    &self.game
+  }
+
+  // MARK: subscribe()
+  /// Registers a subscriber for every episode's events from now on.
+  pub fn subscribe(&mut self, subscriber: Box<dyn Subscriber>) {
+    self.game.bus.subscribe(subscriber);
+  }
+
+  // MARK: reset()
+  /// Starts a new episode on `seed`; the same seed and actions always play out the same.
+  pub fn reset(&mut self, seed: u64) -> Observation {
+    let bus = std::mem::take(&mut self.game.bus);
+    self.game = Game::with_seed(seed);
+    self.game.bus = bus;
+    self.game.game_length = self.length;
+    self.game.ruleset = self.ruleset.clone();
+    self.game.start();
+    self.worth = self.game.net_worth(Valuation::Current).total();
+    self.observe()
+  }
+
+  // MARK: step()
+  /// Takes an action by index, returning the new observation, the reward and whether
+  /// the episode is over. Actions that can't be taken right now change nothing.
+  pub fn step(&mut self, action: usize) -> (Observation, f32, bool) {
+    if !self.game.game_over && self.is_valid(action) {
+      self.apply(action);
+    }
+    let worth = self.game.net_worth(Valuation::Current).total();
+    let reward = (worth - self.worth) as f32;
+    self.worth = worth;
+    (self.observe(), reward, self.game.game_over)
+  }
+
+  // MARK: valid_actions()
+  /// Which actions would do something right now, for masking a policy's choices.
+  pub fn valid_actions(&self) -> [bool; ACTION_COUNT] {
+    std::array::from_fn(|action| self.is_valid(action))
+  }
+
+  fn is_valid(&self, action: usize) -> bool {
+    let game = &self.game;
+    let drugs = get_drug_list();
+    match action {
+      0 => true,
+      a if a < BUY_MAX => get_location_list()[a - TRAVEL] != game.location,
+      a if a < SELL_ALL => game.max_buy(drugs[a - BUY_MAX]) > 0,
+      a if a < REPAY => game.inventory.get_amount(drugs[a - SELL_ALL]).unwrap_or(0) > 0,
+      REPAY => !game.debt.is_zero() && !game.cash.is_zero(),
+      ACCEPT_OFFER => game
+        .event
+        .as_ref()
+        .and_then(|event| event.e_offer)
+        .is_some_and(|(_, price)| game.cash >= price),
+      _ => false,
+    }
+  }
+
+  fn apply(&mut self, action: usize) {
+    let game = &mut self.game;
+    let drugs = get_drug_list();
+    match action {
+      0 => game.wait(),
+      a if a < BUY_MAX => game.travel(get_location_list()[a - TRAVEL]),
+      a if a < SELL_ALL => drop(game.buy_max(drugs[a - BUY_MAX])),
+      a if a < REPAY => drop(game.sell_all(drugs[a - SELL_ALL])),
+      REPAY => drop(game.repay_debt(game.debt.min(game.cash))),
+      ACCEPT_OFFER => game.accept_offer(),
+      _ => {}
+    }
+  }
+
+  // MARK: observe()
+  pub fn observe(&self) -> Observation {
+    let game = &self.game;
+    let mut obs = [0.0; OBSERVATION_SIZE];
+    let mut i = 0;
+    let mut push = |value: f32| {
+      obs[i] = value;
+      i += 1;
+    };
+    for drug in get_drug_list() {
+      push(game.prices[drug.as_index()] as f32);
+    }
+    for drug in get_drug_list() {
+      push(game.inventory.get_amount(drug).unwrap_or(0) as f32);
+    }
+    push(game.cash.get() as f32);
+    push(game.debt.get() as f32);
+    push(game.days_left.map_or(-1.0, |days| days as f32));
+    push(game.free_space() as f32);
+    push(game.heat.level());
+    for location in get_location_list() {
+      push(f32::from(game.location == location));
+    }
+    let e_type = game.event.as_ref().map(|event| &event.e_type);
+    for flag in EventType::ALL {
+      push(f32::from(e_type == Some(&flag)));
+    }
+    obs
+  }
+}
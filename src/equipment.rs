@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use crate::inventory::InventoryError;
+use crate::money::Money;
+
+// MARK: equipment constants
+pub const BASE_CAPACITY: u32 = 100;
+const BASE_COMBAT_ODDS: f32 = 0.05;
+const MAX_COMBAT_ODDS: f32 = 0.75;
+const MAX_PROTECTION: f32 = 0.75;
+const MAX_POLICE_EVASION: f32 = 0.6;
+
+// MARK: Equipment enum
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Equipment {
+  Knife,
+  Gun,
+  BodyArmour,
+  BigCoat,
+  BurnerPhone,
+}
+
+impl Equipment {
+  // MARK: price()
+  pub fn price(&self) -> Money {
+    Money::new(match self {
+      Equipment::Knife => 300,
+      Equipment::Gun => 2500,
+      Equipment::BodyArmour => 1500,
+      Equipment::BigCoat => 800,
+      Equipment::BurnerPhone => 500,
+    })
+  }
+
+  // MARK: resale_price()
+  pub fn resale_price(&self) -> Money {
+    Money::new(self.price().get() / 2)
+  }
+
+  // MARK: combat_bonus()
+  /// Added to the odds of fighting off muggers and cops.
+  pub fn combat_bonus(&self) -> f32 {
+    match self {
+      Equipment::Knife => 0.1,
+      Equipment::Gun => 0.3,
+      _ => 0.0,
+    }
+  }
+
+  // MARK: protection()
+  /// Fraction of drugs and cash kept out of a mugger's hands.
+  pub fn protection(&self) -> f32 {
+    match self {
+      Equipment::BodyArmour => 0.4,
+      Equipment::Knife => 0.1,
+      _ => 0.0,
+    }
+  }
+
+  // MARK: capacity_bonus()
+  pub fn capacity_bonus(&self) -> u32 {
+    match self {
+      Equipment::BigCoat => 50,
+      _ => 0,
+    }
+  }
+
+  // MARK: police_evasion()
+  /// Chance that a police stop never happens at all.
+  pub fn police_evasion(&self) -> f32 {
+    match self {
+      Equipment::BurnerPhone => 0.3,
+      _ => 0.0,
+    }
+  }
+
+  // MARK: description()
+  pub fn description(&self) -> &'static str {
+    match self {
+      Equipment::Knife => "Better odds in a fight, loses you a little less to muggers",
+      Equipment::Gun => "Much better odds against muggers and cops",
+      Equipment::BodyArmour => "Muggers get away with a lot less",
+      Equipment::BigCoat => "Room for 50 more units of product",
+      Equipment::BurnerPhone => "A tip-off call lets you steer clear of some police stops",
+    }
+  }
+}
+
+// MARK: equipment display
+impl std::fmt::Display for Equipment {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Equipment::Knife => write!(f, "Knife"),
+      Equipment::Gun => write!(f, "Gun"),
+      Equipment::BodyArmour => write!(f, "Body Armour"),
+      Equipment::BigCoat => write!(f, "Big Coat"),
+      Equipment::BurnerPhone => write!(f, "Burner Phone"),
+    }
+  }
+}
+
+// MARK: get_equipment_list()
+pub fn get_equipment_list() -> [Equipment; 5] {
+  [
+    Equipment::Knife,
+    Equipment::Gun,
+    Equipment::BodyArmour,
+    Equipment::BigCoat,
+    Equipment::BurnerPhone,
+  ]
+}
+
+// MARK: - EquipmentInventory struct
+#[derive(Debug, Default, Clone)]
+pub struct EquipmentInventory {
+  items: HashMap<Equipment, u32>,
+}
+
+impl EquipmentInventory {
+  // MARK: add()
+  pub fn add(&mut self, item: Equipment) {
+    *self.items.entry(item).or_insert(0) += 1;
+  }
+
+  // MARK: remove()
+  pub fn remove(&mut self, item: Equipment) -> Result<(), InventoryError> {
+    match self.items.get_mut(&item) {
+      Some(amt) if *amt > 0 => {
+        *amt -= 1;
+        Ok(())
+      }
+      _ => Err(InventoryError::InsufficientAmount),
+    }
+  }
+
+  // MARK: get_amount()
+  pub fn get_amount(&self, item: Equipment) -> u32 {
+    self.items.get(&item).copied().unwrap_or(0)
+  }
+
+  // MARK: has_items()
+  pub fn has_items(&self) -> bool {
+    self.items.values().any(|amt| *amt > 0)
+  }
+
+  // MARK: resale_value()
+  pub fn resale_value(&self) -> Money {
+    get_equipment_list()
+      .iter()
+      .map(|item| item.resale_price().times(self.get_amount(*item)))
+      .sum()
+  }
+
+  // MARK: total()
+  fn total(&self, stat: impl Fn(&Equipment) -> f32) -> f32 {
+    get_equipment_list()
+      .iter()
+      .map(|item| stat(item) * self.get_amount(*item) as f32)
+      .sum()
+  }
+
+  // MARK: combat_odds()
+  pub fn combat_odds(&self) -> f32 {
+    (BASE_COMBAT_ODDS + self.total(Equipment::combat_bonus)).min(MAX_COMBAT_ODDS)
+  }
+
+  // MARK: mugging_protection()
+  pub fn mugging_protection(&self) -> f32 {
+    self.total(Equipment::protection).min(MAX_PROTECTION)
+  }
+
+  // MARK: capacity()
+  pub fn capacity(&self) -> u32 {
+    BASE_CAPACITY
+      + get_equipment_list()
+        .iter()
+        .map(|item| item.capacity_bonus() * self.get_amount(*item))
+        .sum::<u32>()
+  }
+
+  // MARK: police_evasion()
+  pub fn police_evasion(&self) -> f32 {
+    self
+      .total(Equipment::police_evasion)
+      .min(MAX_POLICE_EVASION)
+  }
+}
@@ -2,16 +2,28 @@ use core::ops::AddAssign;
 use std::collections::HashMap;
 
 use rand::Rng;
+use rand::distr::Distribution;
+use rand::distr::weighted::WeightedIndex;
 use thousands::Separable;
 
-use crate::drugs::{Drug, get_rand_drug, get_rand_prices};
+use crate::drugs::{Drug, get_rand_drug};
+use crate::equipment::{Equipment, EquipmentInventory, get_equipment_list};
+use crate::heat::Heat;
 use crate::inventory::Inventory;
+use crate::locations::EventTable;
+use crate::money::Money;
+use crate::rumours::{RUMOUR_RELIABILITY, Rumour};
 
-const EVENT_CHANCE: f32 = 0.15;
+const HEAT_EVENT_CHANCE: f32 = 0.25; // extra event chance at max heat
+const HEAT_POLICE_WEIGHT: f32 = 4.0; // extra police event weight at max heat
 const BUSTED_DRUGS_MIN: usize = 1;
 const BUSTED_DRUGS_MAX: usize = 6;
 const MUGGING_DRUGS_MIN: usize = 1;
 const MUGGING_DRUGS_MAX: usize = 4;
+const RAIDED_DRUGS_MIN: usize = 2;
+const RAIDED_DRUGS_MAX: usize = 3;
+const OFFER_DISCOUNT_MIN: f32 = 0.5;
+const OFFER_DISCOUNT_MAX: f32 = 0.9;
 
 // MARK: EventType
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -20,7 +32,30 @@ pub enum EventType {
   DrugBust,
   DrugShipment,
   Mugging,
-  Count, // #types
+  PoliceStop,
+  EquipmentOffer,
+  WarehouseRaid, // Edgewater only
+  Rumour,
+}
+
+impl EventType {
+  pub const ALL: [EventType; 7] = [
+    EventType::DrugBust,
+    EventType::DrugShipment,
+    EventType::Mugging,
+    EventType::PoliceStop,
+    EventType::EquipmentOffer,
+    EventType::WarehouseRaid,
+    EventType::Rumour,
+  ];
+
+  // MARK: is_police()
+  pub fn is_police(&self) -> bool {
+    matches!(
+      self,
+      EventType::DrugBust | EventType::PoliceStop | EventType::WarehouseRaid
+    )
+  }
 }
 
 // MARK: - Event struct
@@ -29,19 +64,19 @@ pub struct Event {
   pub e_type: EventType,
   pub e_msg: String,
   pub e_drugs: Vec<Drug>,
+  pub e_offer: Option<(Equipment, Money)>,
 }
 
 impl Event {
   // MARK: Event::drug_bust()
-  pub fn drug_bust(prices: &mut [u32; 7]) -> Self {
-    let mut rng = rand::rng();
+  pub fn drug_bust(prices: &mut [u32; 7], rng: &mut impl Rng) -> Self {
     let busted_amt = rng.random_range(BUSTED_DRUGS_MIN..=BUSTED_DRUGS_MAX);
     let mut busted_drugs = Vec::new();
 
     for _ in 0..busted_amt {
-      let drug = get_rand_drug();
+      let drug = get_rand_drug(rng);
       if let Some(price) = prices.get_mut(drug as usize) {
-        *price += *price / 2; // increase price by 50%
+        *price = price.saturating_add(*price / 2); // increase price by 50%
         busted_drugs.push(drug);
       }
     }
@@ -50,12 +85,13 @@ impl Event {
       e_type: EventType::DrugBust,
       e_msg: "Cops made a huge bust! Prices have skyrocketed!".to_string(),
       e_drugs: busted_drugs,
+      ..Default::default()
     }
   }
 
   // MARK: Event::drug_shipment()
-  pub fn drug_shipment(prices: &mut [u32; 7]) -> Self {
-    let drug = get_rand_drug();
+  pub fn drug_shipment(prices: &mut [u32; 7], rng: &mut impl Rng) -> Self {
+    let drug = get_rand_drug(rng);
     if let Some(price) = prices.get_mut(drug as usize) {
       *price = (*price / 2).max(1); // decrease price by 50%, but ensure it's at least 1
     }
@@ -67,33 +103,174 @@ impl Event {
         drug
       ),
       e_drugs: vec![drug],
+      ..Default::default()
+    }
+  }
+
+  // MARK: Event::warehouse_raid()
+  pub fn warehouse_raid(prices: &mut [u32; 7], rng: &mut impl Rng) -> Self {
+    let raided_amt = rng.random_range(RAIDED_DRUGS_MIN..=RAIDED_DRUGS_MAX);
+    let mut raided_drugs: Vec<Drug> = Vec::new();
+
+    for _ in 0..raided_amt {
+      let drug = get_rand_drug(rng);
+      if raided_drugs.contains(&drug) {
+        continue;
+      }
+      if let Some(price) = prices.get_mut(drug as usize) {
+        *price = price.saturating_mul(2); // double the price
+        raided_drugs.push(drug);
+      }
+    }
+
+    let list = raided_drugs
+      .iter()
+      .map(|drug| drug.to_string())
+      .collect::<Vec<_>>()
+      .join(" and ");
+    Self {
+      e_type: EventType::WarehouseRaid,
+      e_msg: format!(
+        "Cops raided a warehouse down at the docks! {} supply has dried up!",
+        list
+      ),
+      e_drugs: raided_drugs,
+      ..Default::default()
+    }
+  }
+
+  // MARK: Event::from_rumour()
+  pub fn from_rumour(rumour: &Rumour) -> Self {
+    let e_msg = match rumour.e_type {
+      EventType::DrugBust => format!(
+        "Cops made a huge bust! {} prices have skyrocketed!",
+        rumour.drug
+      ),
+      _ => format!(
+        "A huge shipment just came in! {} prices have bottomed out!",
+        rumour.drug
+      ),
+    };
+
+    Self {
+      e_type: rumour.e_type.clone(),
+      e_msg,
+      e_drugs: vec![rumour.drug],
+      ..Default::default()
+    }
+  }
+
+  // MARK: Event::rumour()
+  pub fn rumour(rumour: &Rumour, today: u32, from_informant: bool) -> Self {
+    let source = if from_informant {
+      "Your informant whispers"
+    } else {
+      "You overhear a rumour"
+    };
+
+    Self {
+      e_type: EventType::Rumour,
+      e_msg: format!("{}: {}", source, rumour.describe(today)),
+      ..Default::default()
     }
   }
 
   // MARK: Event::mugging()
-  pub fn mugging(held_inv: &mut Inventory, held_cash: &mut u32) -> Self {
-    let mut rng = rand::rng();
-    let mugged_map = steal_drugs(held_inv, &mut rng);
-    let cash_taken = steal_cash(held_cash, &mut rng);
+  pub fn mugging(
+    held_inv: &mut Inventory,
+    held_cash: &mut Money,
+    equipment: &EquipmentInventory,
+    cash_ratio: f32,
+    rng: &mut impl Rng,
+  ) -> Self {
+    if rng.random::<f32>() < equipment.combat_odds() {
+      return Self {
+        e_type: EventType::Mugging,
+        e_msg: "Someone tried to mug you, but you fought them off!".to_string(),
+        ..Default::default()
+      };
+    }
+    let protection = equipment.mugging_protection();
+    let mugged_map = steal_drugs(held_inv, protection, rng);
+    let cash_taken = steal_cash(held_cash, cash_ratio, protection, rng);
     let e_msg = create_mugging_message(&mugged_map, *held_cash, cash_taken);
 
     Self {
       e_type: EventType::Mugging,
       e_msg,
       e_drugs: mugged_map.keys().cloned().collect(),
+      ..Default::default()
+    }
+  }
+
+  // MARK: Event::police_stop()
+  pub fn police_stop(
+    held_inv: &mut Inventory,
+    equipment: &EquipmentInventory,
+    heat: &mut Heat,
+    rng: &mut impl Rng,
+  ) -> Self {
+    let (e_msg, e_drugs) = if !held_inv.has_items() {
+      (
+        "The cops stopped and searched you, but you were clean.".to_string(),
+        Vec::new(),
+      )
+    } else if rng.random::<f32>() < equipment.combat_odds() {
+      heat.on_police_fight();
+      (
+        "The cops tried to stop you, but you fought your way out!".to_string(),
+        Vec::new(),
+      )
+    } else {
+      let seized_map = steal_drugs(held_inv, 0.0, rng);
+      let e_msg = if seized_map.is_empty() {
+        "The cops stopped you, but let you go with a warning.".to_string()
+      } else {
+        format!(
+          "The cops stopped you and seized {}!",
+          create_drug_list(&seized_map)
+        )
+      };
+      (e_msg, seized_map.keys().cloned().collect())
+    };
+
+    Self {
+      e_type: EventType::PoliceStop,
+      e_msg,
+      e_drugs,
+      ..Default::default()
+    }
+  }
+
+  // MARK: Event::equipment_offer()
+  pub fn equipment_offer(rng: &mut impl Rng) -> Self {
+    let items = get_equipment_list();
+    let item = items[rng.random_range(0..items.len())];
+    let discount = rng.random_range(OFFER_DISCOUNT_MIN..=OFFER_DISCOUNT_MAX);
+    let price = item.price().scale(discount).max(Money::new(1));
+
+    Self {
+      e_type: EventType::EquipmentOffer,
+      e_msg: format!("A shady stranger offers you a {} for {}.", item, price),
+      e_offer: Some((item, price)),
+      ..Default::default()
     }
   }
 }
 
 // MARK: - steal_drugs() [helper]
-fn steal_drugs(held_inv: &mut Inventory, rng: &mut impl Rng) -> HashMap<Drug, u32> {
+fn steal_drugs(
+  held_inv: &mut Inventory,
+  protection: f32,
+  rng: &mut impl Rng,
+) -> HashMap<Drug, u32> {
   let mut mugged_map = HashMap::new();
 
   if held_inv.has_items() {
     (0..rng.random_range(MUGGING_DRUGS_MIN..=MUGGING_DRUGS_MAX)).for_each(|_| {
-      let drug = get_rand_drug(); // Get random drug once
+      let drug = get_rand_drug(rng); // Get random drug once
       if let Some(held_amt) = held_inv.get_amount(drug).filter(|&amt| amt > 0) {
-        let mugged_amt = rng.random_range(1..=held_amt);
+        let mugged_amt = protect(rng.random_range(1..=held_amt) as u64, protection) as u32;
         mugged_map.entry(drug).or_insert(0).add_assign(mugged_amt);
         held_inv.remove(drug, mugged_amt).unwrap_or_default();
       }
@@ -104,72 +281,109 @@ fn steal_drugs(held_inv: &mut Inventory, rng: &mut impl Rng) -> HashMap<Drug, u3
 }
 
 // MARK: - steal_cash() [helper]
-fn steal_cash(held_cash: &mut u32, rng: &mut impl Rng) -> u32 {
-  if rng.random::<f32>() < 0.5 && *held_cash > 1 {
-    let max_cash_taken = ((*held_cash - 1) / 4).max(1);
-    let cash_taken = rng.random_range(1..=max_cash_taken);
-    *held_cash = held_cash.saturating_sub(cash_taken);
+fn steal_cash(
+  held_cash: &mut Money,
+  cash_ratio: f32,
+  protection: f32,
+  rng: &mut impl Rng,
+) -> Money {
+  if rng.random::<f32>() < 0.5 && *held_cash > Money::new(1) {
+    let spare = *held_cash - Money::new(1);
+    let max_cash_taken = spare.scale(cash_ratio).get().clamp(1, spare.get());
+    let cash_taken = Money::new(protect(rng.random_range(1..=max_cash_taken), protection));
+    *held_cash -= cash_taken;
     cash_taken
   } else {
-    0
+    Money::ZERO
   }
 }
 
+// MARK: - protect() [helper]
+fn protect(amount: u64, protection: f32) -> u64 {
+  ((amount as f64 * (1.0 - protection) as f64).ceil() as u64).clamp(1, amount)
+}
+
+// MARK: - create_drug_list() [helper]
+fn create_drug_list(drug_map: &HashMap<Drug, u32>) -> String {
+  drug_map
+    .iter()
+    .map(|(drug, count)| format!("{} {}", count.separate_with_commas(), drug))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
 // MARK: - create_mugging_message() [helper]
 fn create_mugging_message(
   mugged_map: &HashMap<Drug, u32>,
-  cash_before: u32,
-  cash_taken: u32,
+  cash_left: Money,
+  cash_taken: Money,
 ) -> String {
-  match (mugged_map.is_empty(), cash_before, cash_taken) {
-    (true, _, 0) => "You were mugged, but they found nothing to take!".to_string(),
-    (true, cash_before, cash_taken) => {
-      if cash_taken > 0 && cash_before - cash_taken <= 1 {
-        "You were mugged, but they felt bad and left you with a dollar!".to_string()
-      } else {
-        format!(
-          "You were mugged! They took ${}!",
-          cash_taken.separate_with_commas()
-        )
-      }
-    }
-    (false, _, 0) => {
-      let list = mugged_map
-        .iter()
-        .map(|(drug, count)| format!("{} {}", count.separate_with_commas(), drug))
-        .collect::<Vec<_>>()
-        .join(", ");
-      format!("You were mugged! They took {}!", list)
-    }
-    (false, _, cash_taken) => {
-      let list = mugged_map
-        .iter()
-        .map(|(drug, count)| format!("{} {}", count.separate_with_commas(), drug))
-        .collect::<Vec<_>>()
-        .join(", ");
-      format!(
-        "You were mugged! They took {} and ${}!",
-        list,
-        cash_taken.separate_with_commas()
-      )
+  match (mugged_map.is_empty(), cash_taken.is_zero()) {
+    (true, true) => "You were mugged, but they found nothing to take!".to_string(),
+    (true, false) if cash_left <= Money::new(1) => {
+      "You were mugged, but they felt bad and left you with a dollar!".to_string()
     }
+    (true, false) => format!("You were mugged! They took {}!", cash_taken),
+    (false, true) => format!(
+      "You were mugged! They took {}!",
+      create_drug_list(mugged_map)
+    ),
+    (false, false) => format!(
+      "You were mugged! They took {} and {}!",
+      create_drug_list(mugged_map),
+      cash_taken
+    ),
   }
 }
 
+// MARK: - pick_event_type() [helper]
+fn pick_event_type(table: &EventTable, heat: &Heat, rng: &mut impl Rng) -> EventType {
+  let police_weight = 1.0 + heat.level() * HEAT_POLICE_WEIGHT;
+  let weights = table.weights.iter().map(|(e_type, weight)| {
+    if e_type.is_police() {
+      weight * police_weight
+    } else {
+      *weight
+    }
+  });
+  let dist = WeightedIndex::new(weights).expect("event table has a positive weight");
+  table.weights[dist.sample(rng)].0.clone()
+}
+
 // MARK: generate_event()
 pub fn generate_event(game: &mut crate::game::Game) -> Option<Event> {
-  let rand_num = rand::random::<f32>();
-  if rand_num < EVENT_CHANCE {
-    let event_type = rand::rng().random_range(0..EventType::Count as usize);
-    let event = match event_type {
-      0 => Event::drug_bust(&mut game.prices),
-      1 => Event::drug_shipment(&mut game.prices),
-      2 => {
-        let event = Event::mugging(&mut game.inventory, &mut game.cash);
-        game.prices = get_rand_prices();
+  let rng = &mut game.rng;
+  let table = game.location.event_table(game.ruleset.event_chance);
+  let event_chance = table.chance + game.heat.level() * HEAT_EVENT_CHANCE;
+  if rng.random::<f32>() < event_chance {
+    let event = match pick_event_type(&table, &game.heat, rng) {
+      EventType::DrugBust => Event::drug_bust(&mut game.prices, rng),
+      EventType::DrugShipment => Event::drug_shipment(&mut game.prices, rng),
+      EventType::Mugging => {
+        let event = Event::mugging(
+          &mut game.inventory,
+          &mut game.cash,
+          &game.equipment,
+          game.ruleset.mugging_cash_ratio,
+          rng,
+        );
+        game.prices = game
+          .market
+          .prices_at(game.location, &game.ruleset.price_ranges, rng);
         event
       }
-      _ => Event::default(),
+      EventType::PoliceStop => {
+        if rng.random::<f32>() < game.equipment.police_evasion() {
+          return None;
+        }
+        Event::police_stop(&mut game.inventory, &game.equipment, &mut game.heat, rng)
+      }
+      EventType::EquipmentOffer => Event::equipment_offer(rng),
+      EventType::WarehouseRaid => Event::warehouse_raid(&mut game.prices, rng),
+      EventType::Rumour => {
+        let rumour = game.rumours.hear(game.day, RUMOUR_RELIABILITY, rng);
+        Event::rumour(&rumour, game.day, false)
+      }
     };
     Some(event)
   } else {
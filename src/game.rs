@@ -1,40 +1,138 @@
 use eframe::{App, egui};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
 
+use crate::achievements::Achievements;
+use crate::advisor::Advisor;
+use crate::autopilot::Autopilot;
+use crate::basket::{Basket, Order};
+use crate::bus::{EventBus, GameEvent, Subscriber};
 use crate::drugs::*;
+use crate::equipment::{Equipment, EquipmentInventory};
 use crate::events::{Event, generate_event};
+use crate::heat::Heat;
+use crate::highscores::{HighScore, HighScores};
 use crate::inventory::Inventory;
 use crate::locations::*;
+use crate::market::{Market, MarketShock};
+use crate::money::Money;
+use crate::networth::{NetWorth, Valuation};
+use crate::replay::{Action, daily_seed};
+use crate::ruleset::{Difficulty, Ruleset};
+use crate::rumours::{INFORMANT_FEE, INFORMANT_RELIABILITY, Rumours};
+use crate::scenarios::{Outcome, ScenarioRun};
+use crate::stats::Stats;
+use crate::storage;
+use crate::trade::{TradeError, TradeReceipt};
+use crate::tutorial::{TUTORIAL_SEED, Tutorial};
 use crate::ui::*;
+use crate::undo::{UndoEntry, UndoStack};
 
-const INTEREST_RATE: f32 = 0.09;
-
-#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum GameLength {
   #[default]
-  Short = 30,
-  Medium = 180,
-  Long = 360,
+  Short,
+  Medium,
+  Long,
+  Custom(u32),
+  /// Runs until the player retires or goes bankrupt.
+  Endless,
+}
+
+pub const DEFAULT_CUSTOM_DAYS: u32 = 60;
+pub const MAX_CUSTOM_DAYS: u32 = 3650;
+
+impl GameLength {
+  // MARK: GameLength::days()
+  /// Number of days in the game, or `None` for endless mode.
+  pub fn days(&self) -> Option<u32> {
+    match self {
+      GameLength::Short => Some(30),
+      GameLength::Medium => Some(180),
+      GameLength::Long => Some(360),
+      GameLength::Custom(days) => Some(*days),
+      GameLength::Endless => None,
+    }
+  }
+}
+
+// MARK: GameLength display
+impl std::fmt::Display for GameLength {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      GameLength::Short => write!(f, "One Month"),
+      GameLength::Medium => write!(f, "Six Months"),
+      GameLength::Long => write!(f, "One Year"),
+      GameLength::Custom(days) => write!(f, "{} Days", days),
+      GameLength::Endless => write!(f, "Endless"),
+    }
+  }
 }
 
 // MARK: - Game struct
-#[derive(Default, Clone)]
+#[derive(Clone)]
 pub struct Game {
   pub init: bool,
   pub game_over: bool,
   pub game_length: GameLength,
-  pub days_left: u32,
+  pub ruleset: Ruleset,
+  pub seed: u64,
+  pub rng: StdRng,
+  pub daily: bool,
+  /// The date of the daily challenge being played, if any.
+  pub daily_date: String,
+  pub actions: Vec<Action>,
+  pub day: u32,
+  pub days_left: Option<u32>,
+  pub retired: bool,
   pub location: Location,
   pub inventory: Inventory,
+  pub equipment: EquipmentInventory,
+  pub show_equipment: bool,
+  pub market: Market,
+  pub rumours: Rumours,
+  pub show_rumours: bool,
+  pub autopilot: Autopilot,
+  pub show_autopilot: bool,
   pub prices: [u32; 7],
   pub last_prices: [u32; 7],
   pub buy_amts: [u32; 7],
   pub sell_amts: [u32; 7],
-  pub cash: u32,
-  pub debt: u32,
-  pub repay_amt: u32,
+  pub basket: Basket,
+  pub advisor: Advisor,
+  pub cash: Money,
+  pub debt: Money,
+  pub repay_amt: Money,
+  /// Net worth when the game started, which profit is measured from.
+  pub starting_worth: i64,
+  pub heat: Heat,
   pub event: Option<Event>,
   pub dev_mode: bool,
   pub selected_drug_idx: usize,
+  pub high_scores: HighScores,
+  pub show_high_scores: bool,
+  /// The table being looked at in the high score window.
+  pub high_score_tab: String,
+  pub high_score_difficulty: Difficulty,
+  pub high_score_advisor: bool,
+  pub player_name: String,
+  pub score_rank: Option<usize>,
+  pub score_saved: bool,
+  pub career: Stats,
+  pub show_stats: bool,
+  pub bus: EventBus,
+  pub achievements: Achievements,
+  pub show_achievements: bool,
+  pub toasts: Vec<Toast>,
+  pub scenario: Option<ScenarioRun>,
+  pub tutorial: Option<Tutorial>,
+  /// Whether this game started as the tutorial, even if it was closed since.
+  pub tutorial_run: bool,
+  pub undo: UndoStack,
+  /// Today's trade events, held back from career stats and achievements
+  /// until the trades can no longer be undone.
+  uncommitted: Vec<GameEvent>,
 }
 
 // MARK: App trait impl
@@ -44,86 +142,726 @@ impl App for Game {
   }
 }
 
+impl Default for Game {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 // MARK: - Game impl
 impl Game {
   // MARK: Game::new()
   pub fn new() -> Game {
-    let rand_prices = get_rand_prices();
+    Game::with_seed(rand::random())
+  }
+
+  // MARK: Game::with_seed()
+  pub fn with_seed(seed: u64) -> Game {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let ruleset = Ruleset::default();
+    let rand_prices = get_rand_prices(&ruleset.price_ranges, &mut rng);
     Game {
       init: true,
+      seed,
+      rng,
+      daily: false,
+      daily_date: String::new(),
+      actions: Vec::new(),
       game_over: false,
       location: Location::default(),
       inventory: Inventory::default(),
+      equipment: EquipmentInventory::default(),
+      show_equipment: false,
+      market: Market::default(),
+      rumours: Rumours::default(),
+      show_rumours: false,
+      autopilot: Autopilot::default(),
+      show_autopilot: false,
       prices: rand_prices,
       last_prices: rand_prices,
       buy_amts: [0; 7],
       sell_amts: [0; 7],
-      cash: 2000,
-      debt: 2000,
-      repay_amt: 0,
+      basket: Basket::default(),
+      advisor: Advisor::default(),
+      cash: ruleset.starting_cash,
+      debt: ruleset.starting_debt,
+      repay_amt: Money::ZERO,
+      starting_worth: 0,
+      heat: Heat::default(),
       game_length: GameLength::Short,
-      days_left: GameLength::Short as u32,
+      ruleset,
+      day: 0,
+      days_left: GameLength::Short.days(),
+      retired: false,
       event: None,
       dev_mode: false,
       selected_drug_idx: 0,
+      high_scores: HighScores::default(),
+      show_high_scores: false,
+      high_score_tab: GameLength::default().to_string(),
+      high_score_difficulty: Difficulty::default(),
+      high_score_advisor: Ruleset::default().advisor_enabled,
+      player_name: String::new(),
+      score_rank: None,
+      score_saved: false,
+      career: Stats::default(),
+      show_stats: false,
+      bus: EventBus::default(),
+      achievements: Achievements::default(),
+      show_achievements: false,
+      toasts: Vec::new(),
+      scenario: None,
+      tutorial: None,
+      tutorial_run: false,
+      undo: UndoStack::default(),
+      uncommitted: Vec::new(),
     }
   }
 
+  // MARK: Game::start()
+  /// Leaves the init screen, setting the game up from the chosen length and ruleset,
+  /// or from the chosen scenario.
+  pub fn start(&mut self) {
+    let scenario = self.scenario.as_ref().map(|run| run.scenario.clone());
+    if let Some(scenario) = &scenario {
+      self.game_length = GameLength::Custom(scenario.days);
+      self.ruleset = Ruleset {
+        starting_cash: scenario.cash,
+        starting_debt: scenario.debt,
+        ..scenario.ruleset.clone()
+      };
+      self.seed = scenario.seed.unwrap_or(self.seed);
+    }
+    // every draw in a run comes from its seed, so a replay of the same actions matches
+    self.rng = StdRng::seed_from_u64(self.seed);
+    self.actions.clear();
+    self.cash = self.ruleset.starting_cash;
+    self.debt = self.ruleset.starting_debt;
+    self.days_left = self.game_length.days();
+    self.prices = get_rand_prices(&self.ruleset.price_ranges, &mut self.rng);
+    self.last_prices = self.prices;
+    if let Some(scenario) = scenario {
+      self.location = scenario.location;
+      for (drug, amount) in scenario.inventory {
+        self.inventory.add(
+          drug,
+          amount,
+          Money::from(get_drug_price(drug, &self.prices)),
+        );
+      }
+    }
+    self.starting_worth = self.net_worth(Valuation::Current).total();
+    self.advisor.record(self.location, self.day, self.prices);
+    self.init = false;
+  }
+
+  // MARK: Game::start_daily()
+  /// Starts today's daily challenge, which everyone plays with the same seed and rules.
+  pub fn start_daily(&mut self) {
+    self.start_daily_on(&storage::today());
+  }
+
+  // MARK: Game::start_daily_on()
+  /// Starts the daily challenge of any date, e.g. to replay it.
+  pub fn start_daily_on(&mut self, date: &str) {
+    self.daily = true;
+    self.daily_date = date.to_string();
+    self.scenario = None;
+    self.seed = daily_seed(date);
+    self.ruleset = Ruleset::competitive();
+    self.start();
+  }
+
+  // MARK: Game::start_tutorial()
+  /// Starts a guided one-month game on the tutorial's fixed seed.
+  pub fn start_tutorial(&mut self) {
+    self.tutorial = Some(Tutorial::default());
+    self.tutorial_run = true;
+    self.scenario = None;
+    self.daily = false;
+    self.seed = TUTORIAL_SEED;
+    self.game_length = GameLength::Short;
+    self.ruleset = Ruleset::default();
+    self.start();
+  }
+
   pub fn toggle_dev_mode(&mut self) {
     self.dev_mode = !self.dev_mode;
   }
 
+  // MARK: Game::emit()
+  /// Hands an event to the built-in subscribers, then queues it for the frontend.
+  /// Trades reach career stats and achievements only once the day commits them.
+  fn emit(&mut self, event: GameEvent) {
+    if matches!(
+      event,
+      GameEvent::Bought { .. } | GameEvent::Sold { .. } | GameEvent::DebtRepaid { .. }
+    ) {
+      self.uncommitted.push(event.clone());
+    } else {
+      self.career.notify(&event);
+      self.achievements.notify(&event);
+    }
+    if let Some(run) = self.scenario.as_mut() {
+      run.notify(&event);
+    }
+    if let Some(tutorial) = self.tutorial.as_mut() {
+      tutorial.notify(&event);
+    }
+    self.bus.publish(event);
+  }
+
+  // MARK: Game::commit_trades()
+  /// Makes today's trades final: they can't be undone any more, and career
+  /// stats and achievements finally hear about them.
+  fn commit_trades(&mut self) {
+    self.undo.clear();
+    for event in std::mem::take(&mut self.uncommitted) {
+      self.career.notify(&event);
+      self.achievements.notify(&event);
+    }
+  }
+
   // MARK: Game::travel()
   pub fn travel(&mut self, location: Location) {
-    if self.location == location {
+    if self.game_over || self.location == location {
       return;
     }
-    self.days_left = self.days_left.saturating_sub(1);
+    self.actions.push(Action::Travel(location));
+    self.commit_trades();
+    let from = self.location;
     self.location = location;
+    self.emit(GameEvent::Traveled { from, to: location });
+    self.advance_day(false);
+  }
+
+  // MARK: Game::run_autopilot()
+  /// Lets the autopilot take its next action, if one is due at `now`, in seconds.
+  /// The bot trades on the advisor's tips, so it won't play when the rules turn those off.
+  pub fn run_autopilot(&mut self, now: f64) {
+    if !self.ruleset.advisor_enabled {
+      self.autopilot.stop();
+      return;
+    }
+    let mut autopilot = std::mem::take(&mut self.autopilot);
+    autopilot.tick(self, now);
+    self.autopilot = autopilot;
+  }
+
+  // MARK: Game::wait()
+  pub fn wait(&mut self) {
+    if self.game_over {
+      return;
+    }
+    self.actions.push(Action::Wait);
+    self.commit_trades();
+    self.emit(GameEvent::Waited {
+      location: self.location,
+    });
+    self.advance_day(true);
+  }
+
+  // MARK: Game::advance_day()
+  fn advance_day(&mut self, stayed: bool) {
+    let location = self.location;
+    self.day += 1;
+    self.days_left = self.days_left.map(|days| days.saturating_sub(1));
     self.last_prices = self.prices;
-    self.prices = get_rand_prices();
-    self.debt += (self.debt as f32 * INTEREST_RATE) as u32;
+    self.market.tick();
 
-    if let Some(event) = generate_event(self) {
+    // rumoured events hit their market whether or not the player is there to see it
+    let mut rumoured_event = None;
+    for rumour in self.rumours.take_due(self.day) {
+      let event = Event::from_rumour(&rumour);
+      self
+        .market
+        .add_shocks(MarketShock::from_event(&event, rumour.location));
+      if rumour.location == location {
+        rumoured_event = Some(event);
+      }
+    }
+
+    self.prices = if stayed {
+      self.market.evolve_prices(
+        location,
+        &self.prices,
+        &self.ruleset.price_ranges,
+        &mut self.rng,
+      )
+    } else {
+      self
+        .market
+        .prices_at(location, &self.ruleset.price_ranges, &mut self.rng)
+    };
+    let interest = self.debt.scale(self.ruleset.interest_rate);
+    self.debt += interest;
+    if !interest.is_zero() {
+      self.emit(GameEvent::InterestCharged { amount: interest });
+    }
+    self.heat.decay();
+    self.heat.on_carry(self.inventory.total_amount());
+
+    let cash_before = self.cash;
+    let inventory_before = self.inventory.clone();
+    let prices_before = self.prices;
+    if rumoured_event.is_some() {
+      self.event = rumoured_event;
+    } else if let Some(event) = generate_event(self) {
+      self
+        .market
+        .add_shocks(MarketShock::from_event(&event, location));
       self.event = Some(event);
     } else {
       self.event = None;
     }
+
+    if let Some(event) = self.event.clone() {
+      let drugs_lost: Money = get_drug_list()
+        .iter()
+        .map(|&drug| {
+          let before = inventory_before.get_amount(drug).unwrap_or(0);
+          let after = self.inventory.get_amount(drug).unwrap_or(0);
+          Money::from(get_drug_price(drug, &prices_before)).times(before.saturating_sub(after))
+        })
+        .sum();
+      let lost = (cash_before - self.cash) + drugs_lost;
+      self.emit(GameEvent::EventTriggered { event, lost });
+    }
+
+    self.advisor.record(location, self.day, self.prices);
+    self.check_game_over(true);
   }
 
   // MARK: Game::buy()
-  pub fn buy(&mut self, drug: Drug, buy_amt: u32) {
-    let price = get_drug_price(drug, &self.prices);
-    if buy_amt > 0 && self.cash >= price * buy_amt {
-      self.cash -= price * buy_amt;
-      self.inventory.add(drug, buy_amt, price);
+  pub fn buy(&mut self, drug: Drug, buy_amt: u32) -> Result<TradeReceipt, TradeError> {
+    if self.game_over {
+      return Err(TradeError::GameOver);
     }
+    self.actions.push(Action::Buy(drug, buy_amt));
+    if buy_amt == 0 {
+      return Err(TradeError::InvalidAmount);
+    }
+    if buy_amt > self.free_space() {
+      return Err(TradeError::InsufficientSpace);
+    }
+    let price = Money::from(get_drug_price(drug, &self.prices));
+    let total = price.times(buy_amt);
+    let cash = self
+      .cash
+      .checked_sub(total)
+      .ok_or(TradeError::InsufficientCash)?;
+    let receipt = TradeReceipt::Bought {
+      drug,
+      amount: buy_amt,
+      price,
+      total,
+    };
+    let undo = UndoEntry::capture(self, Some(drug), receipt);
+    self.cash = cash;
+    self.inventory.add(drug, buy_amt, price);
+    self.heat.on_trade(total);
+    self.undo.push(undo);
+    self.emit(GameEvent::Bought {
+      drug,
+      amount: buy_amt,
+      price,
+    });
+    Ok(receipt)
   }
 
   // MARK: Game::sell()
-  pub fn sell(&mut self, drug: Drug, sell_amt: u32) {
-    let price = get_drug_price(drug, &self.prices);
-    if sell_amt > 0 && self.inventory.get_amount(drug).unwrap_or(0) >= sell_amt {
-      self.cash += price * sell_amt;
-      self.inventory.remove(drug, sell_amt).unwrap();
-      if self.inventory.get_amount(drug).unwrap_or(0) == 0 {
-        self.inventory.reset_cost(drug);
+  pub fn sell(&mut self, drug: Drug, sell_amt: u32) -> Result<TradeReceipt, TradeError> {
+    let receipt = self.sell_stock(drug, sell_amt)?;
+    self.check_game_over(false);
+    Ok(receipt)
+  }
+
+  // MARK: Game::sell_stock()
+  /// Sells without checking whether that ended the game, for trades that go
+  /// through together and are judged once at the end.
+  fn sell_stock(&mut self, drug: Drug, sell_amt: u32) -> Result<TradeReceipt, TradeError> {
+    if self.game_over {
+      return Err(TradeError::GameOver);
+    }
+    self.actions.push(Action::Sell(drug, sell_amt));
+    if sell_amt == 0 {
+      return Err(TradeError::InvalidAmount);
+    }
+    let price = Money::from(get_drug_price(drug, &self.prices));
+    let total = price.times(sell_amt);
+    let receipt = TradeReceipt::Sold {
+      drug,
+      amount: sell_amt,
+      price,
+      total,
+    };
+    let undo = UndoEntry::capture(self, Some(drug), receipt);
+    let cost = self.inventory.get_cost(drug).unwrap_or_default();
+    self.inventory.remove(drug, sell_amt)?;
+    if self.inventory.get_amount(drug).unwrap_or(0) == 0 {
+      self.inventory.reset_cost(drug);
+    }
+    self.cash += total;
+    self.heat.on_trade(total);
+    self.undo.push(undo);
+    self.emit(GameEvent::Sold {
+      drug,
+      amount: sell_amt,
+      price,
+      cost,
+    });
+    Ok(receipt)
+  }
+
+  // MARK: Game::place()
+  /// Places one order of a basket, leaving the check for game over until they all have.
+  fn place(&mut self, order: Order) -> Result<TradeReceipt, TradeError> {
+    match order {
+      Order::Buy(drug, amount) => self.buy(drug, amount),
+      Order::Sell(drug, amount) => self.sell_stock(drug, amount),
+    }
+  }
+
+  // MARK: Game::execute_basket()
+  /// Executes every staged order, or none of them if any would fail.
+  pub fn execute_basket(&mut self) -> Result<Vec<TradeReceipt>, TradeError> {
+    if self.game_over {
+      return Err(TradeError::GameOver);
+    }
+    if self.basket.is_empty() {
+      return Err(TradeError::InvalidAmount);
+    }
+    self.basket.preview(self)?;
+    // the preview vouches for every order, but they're tried on a copy first,
+    // which announces nothing, so one failing anyway leaves no trace
+    let mut trial = self.clone();
+    for order in self.basket.orders() {
+      trial.place(order)?;
+    }
+    let mark = self.undo.len();
+    let receipts = self
+      .basket
+      .orders()
+      .into_iter()
+      .map(|order| self.place(order))
+      .collect::<Result<Vec<_>, _>>()?;
+    self.undo.join(mark);
+    self.basket.clear();
+    self.check_game_over(false);
+    Ok(receipts)
+  }
+
+  // MARK: Game::max_buy()
+  /// The most of a drug that cash and carrying space both allow.
+  pub fn max_buy(&self, drug: Drug) -> u32 {
+    let price = Money::from(get_drug_price(drug, &self.prices));
+    if price.is_zero() {
+      return self.free_space();
+    }
+    self.cash.quantity_at(price).min(self.free_space())
+  }
+
+  // MARK: Game::buy_max()
+  pub fn buy_max(&mut self, drug: Drug) -> Result<TradeReceipt, TradeError> {
+    if self.free_space() == 0 {
+      return Err(TradeError::InsufficientSpace);
+    }
+    match self.max_buy(drug) {
+      0 => Err(TradeError::InsufficientCash),
+      amount => self.buy(drug, amount),
+    }
+  }
+
+  // MARK: Game::sell_all()
+  pub fn sell_all(&mut self, drug: Drug) -> Result<TradeReceipt, TradeError> {
+    match self.inventory.get_amount(drug).unwrap_or(0) {
+      0 => Err(TradeError::NothingToSell),
+      amount => self.sell(drug, amount),
+    }
+  }
+
+  // MARK: Game::sell_everything()
+  /// Sells every drug on hand that fetches anything here.
+  pub fn sell_everything(&mut self) -> Result<Vec<TradeReceipt>, TradeError> {
+    self.sell_where(|_, _| true)
+  }
+
+  // MARK: Game::sell_profitable()
+  /// Sells every drug that goes for more here than was last paid for it.
+  pub fn sell_profitable(&mut self) -> Result<Vec<TradeReceipt>, TradeError> {
+    if !self.inventory.has_items() {
+      return Err(TradeError::NothingToSell);
+    }
+    match self.sell_where(|price, cost| price > cost) {
+      Err(TradeError::NothingToSell) => Err(TradeError::NoProfit),
+      result => result,
+    }
+  }
+
+  fn sell_where(
+    &mut self,
+    profitable: impl Fn(Money, Money) -> bool,
+  ) -> Result<Vec<TradeReceipt>, TradeError> {
+    let mut receipts = Vec::new();
+    for drug in get_drug_list() {
+      let price = Money::from(get_drug_price(drug, &self.prices));
+      let cost = self.inventory.get_cost(drug).unwrap_or_default();
+      if self.game_over || price.is_zero() || !profitable(price, cost) {
+        continue;
       }
+      if let Ok(receipt) = self.sell_all(drug) {
+        receipts.push(receipt);
+      }
+    }
+    match receipts.is_empty() {
+      true => Err(TradeError::NothingToSell),
+      false => Ok(receipts),
+    }
+  }
+
+  // MARK: Game::free_space()
+  pub fn free_space(&self) -> u32 {
+    self
+      .equipment
+      .capacity()
+      .saturating_sub(self.inventory.total_amount())
+  }
+
+  // MARK: Game::buy_equipment()
+  pub fn buy_equipment(&mut self, item: Equipment) {
+    if self.game_over {
+      return;
+    }
+    self.actions.push(Action::BuyEquipment(item));
+    if let Some(cash) = self.cash.checked_sub(item.price()) {
+      self.commit_trades();
+      self.cash = cash;
+      self.equipment.add(item);
+      self.check_game_over(false);
+    }
+  }
+
+  // MARK: Game::sell_equipment()
+  /// Refused while the drugs being carried wouldn't fit without the item.
+  pub fn sell_equipment(&mut self, item: Equipment) -> Result<(), TradeError> {
+    if self.game_over {
+      return Err(TradeError::GameOver);
+    }
+    self.actions.push(Action::SellEquipment(item));
+    if self.equipment.get_amount(item) == 0 {
+      return Err(TradeError::InsufficientStock);
+    }
+    let capacity = self
+      .equipment
+      .capacity()
+      .saturating_sub(item.capacity_bonus());
+    if self.inventory.total_amount() > capacity {
+      return Err(TradeError::StillNeeded);
+    }
+    self.equipment.remove(item)?;
+    self.commit_trades();
+    self.cash += item.resale_price();
+    Ok(())
+  }
+
+  // MARK: Game::accept_offer()
+  pub fn accept_offer(&mut self) {
+    if self.game_over {
+      return;
+    }
+    self.actions.push(Action::AcceptOffer);
+    let Some((item, price)) = self.event.as_ref().and_then(|event| event.e_offer) else {
+      return;
+    };
+    if let Some(cash) = self.cash.checked_sub(price) {
+      self.commit_trades();
+      self.cash = cash;
+      self.equipment.add(item);
+      if let Some(event) = self.event.as_mut() {
+        event.e_offer = None;
+        event.e_msg = format!("You bought the {}.", item);
+      }
+    }
+  }
+
+  // MARK: Game::pay_informant()
+  pub fn pay_informant(&mut self) {
+    if self.game_over {
+      return;
+    }
+    self.actions.push(Action::PayInformant);
+    if let Some(cash) = self.cash.checked_sub(INFORMANT_FEE) {
+      self.commit_trades();
+      self.cash = cash;
+      let rumour = self
+        .rumours
+        .hear(self.day, INFORMANT_RELIABILITY, &mut self.rng);
+      self.event = Some(Event::rumour(&rumour, self.day, true));
+      self.check_game_over(false);
     }
   }
 
   // MARK: Game::repay_debt()
-  pub fn repay_debt(&mut self, amount: u32) {
+  pub fn repay_debt(&mut self, amount: Money) -> Result<TradeReceipt, TradeError> {
+    if self.game_over {
+      return Err(TradeError::GameOver);
+    }
+    self.actions.push(Action::RepayDebt(amount));
+    if self.debt.is_zero() {
+      return Err(TradeError::NothingOwed);
+    }
     let amount = std::cmp::min(amount, self.debt);
-    if self.cash >= amount {
-      self.cash -= amount;
-      self.debt -= amount;
-      self.repay_amt = 0;
+    if amount.is_zero() {
+      return Err(TradeError::InvalidAmount);
+    }
+    let cash = self
+      .cash
+      .checked_sub(amount)
+      .ok_or(TradeError::InsufficientCash)?;
+    let receipt = TradeReceipt::Repaid {
+      amount,
+      remaining: self.debt - amount,
+    };
+    let undo = UndoEntry::capture(self, None, receipt);
+    self.cash = cash;
+    self.debt -= amount;
+    self.repay_amt = Money::ZERO;
+    self.undo.push(undo);
+    self.emit(GameEvent::DebtRepaid {
+      amount,
+      remaining: self.debt,
+    });
+    self.check_game_over(false);
+    Ok(receipt)
+  }
+
+  // MARK: Game::undo()
+  /// Rolls back the latest trade or repayment made today, or the whole of the
+  /// latest basket, restoring it exactly. Returns what was taken back, oldest first.
+  pub fn undo(&mut self) -> Option<Vec<TradeReceipt>> {
+    if self.game_over {
+      return None;
+    }
+    self.actions.push(Action::Undo);
+    let step = self.undo.pop()?;
+    let mut receipts = Vec::new();
+    for entry in step.into_iter().rev() {
+      let receipt = entry.restore(self);
+      // every undoable trade held back exactly one event, so it's the latest one
+      self.uncommitted.pop();
+      self.emit(GameEvent::TradeUndone { receipt });
+      receipts.push(receipt);
+    }
+    receipts.reverse();
+    Some(receipts)
+  }
+
+  // MARK: Game::net_worth()
+  pub fn net_worth(&self, valuation: Valuation) -> NetWorth {
+    NetWorth::of(self, valuation)
+  }
+
+  // MARK: Game::is_bankrupt()
+  /// True when the player can't afford even the cheapest drug and has nothing left to sell.
+  pub fn is_bankrupt(&self) -> bool {
+    let cheapest = self.prices.iter().copied().min().unwrap_or_default();
+    self.cash < Money::from(cheapest) && !self.inventory.has_items() && !self.equipment.has_items()
+  }
+
+  // MARK: Game::check_game_over()
+  /// Bankruptcy only counts at the start of a day, so spending down to nothing
+  /// (e.g. paying off the debt) doesn't end the game on the spot.
+  fn check_game_over(&mut self, new_day: bool) {
+    let verdict = self.scenario.as_ref().and_then(|run| run.judge(self));
+    let bankrupt = new_day && self.is_bankrupt();
+    if verdict.is_none() && self.days_left != Some(0) && !bankrupt {
+      return;
+    }
+    // running out of time or money before the goal is met loses a scenario
+    if let Some(run) = self.scenario.as_mut()
+      && run.outcome.is_none()
+    {
+      run.outcome = Some(verdict.unwrap_or(Outcome::Lost));
+    }
+    self.end_game();
+  }
+
+  // MARK: Game::retire()
+  /// Ends the game on the player's terms, cashing out at today's prices.
+  pub fn retire(&mut self) {
+    if self.game_over {
+      return;
+    }
+    self.actions.push(Action::Retire);
+    self.retired = true;
+    self.end_game();
+  }
+
+  // MARK: Game::score()
+  /// Final net worth, or in endless mode the profit made per day played.
+  pub fn score(&self) -> i64 {
+    let net_worth = self.net_worth(Valuation::Current).total();
+    match self.game_length {
+      GameLength::Endless => (net_worth - self.starting_worth) / self.day.max(1) as i64,
+      _ => net_worth,
+    }
+  }
+
+  // MARK: Game::end_game()
+  /// Marks the game as over and publishes the final score, once.
+  pub fn end_game(&mut self) {
+    if self.game_over {
+      return;
+    }
+    self.game_over = true;
+    self.commit_trades();
+    self.emit(GameEvent::GameEnded {
+      score: self.score(),
+      profit: self.net_worth(Valuation::Current).total() - self.starting_worth,
+      days_played: self.day,
+    });
+  }
+
+  // MARK: Game::is_ranked()
+  /// Whether the game can go in the high score table: scenarios and the
+  /// fixed-seed tutorial can't.
+  pub fn is_ranked(&self) -> bool {
+    self.scenario.is_none() && !self.tutorial_run
+  }
+
+  // MARK: Game::record_high_score()
+  pub fn record_high_score(&mut self) {
+    if self.score_saved || !self.is_ranked() {
+      return;
     }
+    let entry = HighScore {
+      name: self.player_name.trim().to_string(),
+      date: storage::today(),
+      seed: self.seed,
+      cash: self.cash,
+      debt: self.debt,
+      score: self.score(),
+      ruleset: self.ruleset.clone(),
+    };
+    self.score_rank = self.high_scores.add(self.game_length, entry);
+    self.score_saved = true;
+    self.high_scores.save().ok();
   }
 
   // MARK: Game::reset()
+  /// Starts a fresh game, keeping everything that outlives a single run.
   pub fn reset(&mut self) {
+    let high_scores = std::mem::take(&mut self.high_scores);
+    let career = std::mem::take(&mut self.career);
+    let achievements = std::mem::take(&mut self.achievements);
+    let player_name = std::mem::take(&mut self.player_name);
+    let mut bus = std::mem::take(&mut self.bus);
+    bus.drain();
     *self = Game::new();
+    self.bus = bus;
+    self.high_scores = high_scores;
+    self.career = career;
+    self.achievements = achievements;
+    self.player_name = player_name;
   }
 }
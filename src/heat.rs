@@ -0,0 +1,57 @@
+use crate::money::Money;
+
+// MARK: heat constants
+pub const MAX_HEAT: u32 = 100;
+const HEAT_DECAY: u32 = 5;
+const TRADE_HEAT_THRESHOLD: u64 = 2000;
+const TRADE_HEAT_STEP: u64 = 1000;
+const CARRY_HEAT_THRESHOLD: u32 = 50;
+const CARRY_HEAT_STEP: u32 = 25;
+const POLICE_FIGHT_HEAT: u32 = 20;
+
+// MARK: - Heat type wrapper
+/// How much attention the player has drawn from the cops, from 0 to `MAX_HEAT`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Heat(u32);
+
+impl Heat {
+  pub fn get(&self) -> u32 {
+    self.0
+  }
+
+  // MARK: level()
+  /// Heat as a fraction of `MAX_HEAT`, for scaling event odds.
+  pub fn level(&self) -> f32 {
+    self.0 as f32 / MAX_HEAT as f32
+  }
+
+  // MARK: raise()
+  pub fn raise(&mut self, value: u32) {
+    self.0 = self.0.saturating_add(value).min(MAX_HEAT);
+  }
+
+  // MARK: on_trade()
+  pub fn on_trade(&mut self, value: Money) {
+    let value = value.get();
+    if value > TRADE_HEAT_THRESHOLD {
+      self.raise((value / TRADE_HEAT_STEP).min(MAX_HEAT as u64) as u32);
+    }
+  }
+
+  // MARK: on_carry()
+  pub fn on_carry(&mut self, units: u32) {
+    if units > CARRY_HEAT_THRESHOLD {
+      self.raise((units - CARRY_HEAT_THRESHOLD) / CARRY_HEAT_STEP + 1);
+    }
+  }
+
+  // MARK: on_police_fight()
+  pub fn on_police_fight(&mut self) {
+    self.raise(POLICE_FIGHT_HEAT);
+  }
+
+  // MARK: decay()
+  pub fn decay(&mut self) {
+    self.0 = self.0.saturating_sub(HEAT_DECAY);
+  }
+}
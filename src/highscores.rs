@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::GameLength;
+use crate::money::Money;
+use crate::ruleset::{Difficulty, Ruleset};
+use crate::storage;
+
+// MARK: high score constants
+const MAX_SCORES: usize = 10;
+const SCORES_FILE: &str = "highscores.json";
+const SEPARATOR: &str = " · ";
+
+// MARK: table_name()
+/// The table for a game length, difficulty and whether the advisor helped:
+/// runs only compete with others played by the same rules.
+pub fn table_name(length: &str, difficulty: Difficulty, advisor: bool) -> String {
+  let mut name = format!("{}{}{}", length, SEPARATOR, difficulty);
+  if advisor {
+    name.push_str(SEPARATOR);
+    name.push_str("Advisor");
+  }
+  name
+}
+
+// MARK: - HighScore struct
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScore {
+  pub name: String,
+  pub date: String,
+  pub seed: u64,
+  pub cash: Money,
+  pub debt: Money,
+  pub score: i64,
+  #[serde(default)]
+  pub ruleset: Ruleset,
+}
+
+// MARK: - HighScores struct
+/// The best runs for each game length and set of rules, best first.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct HighScores {
+  tables: BTreeMap<String, Vec<HighScore>>,
+}
+
+impl HighScores {
+  // MARK: load()
+  pub fn load() -> Self {
+    let mut scores: Self = storage::load(SCORES_FILE);
+    scores.regroup();
+    scores
+  }
+
+  // MARK: regroup()
+  /// Files every score under its length and rules, moving those saved when
+  /// the tables were split differently.
+  fn regroup(&mut self) {
+    for (name, entries) in std::mem::take(&mut self.tables) {
+      let length = name.split(SEPARATOR).next().unwrap_or_default();
+      for entry in entries {
+        let ruleset = &entry.ruleset;
+        let table = table_name(length, ruleset.difficulty, ruleset.advisor_enabled);
+        self.insert(table, entry);
+      }
+    }
+  }
+
+  // MARK: save()
+  pub fn save(&self) -> std::io::Result<()> {
+    storage::save(SCORES_FILE, self)
+  }
+
+  // MARK: get()
+  pub fn get(&self, length: GameLength, ruleset: &Ruleset) -> &[HighScore] {
+    self.table(&table_name(
+      &length.to_string(),
+      ruleset.difficulty,
+      ruleset.advisor_enabled,
+    ))
+  }
+
+  // MARK: table()
+  /// The table with the given `table_name()`.
+  pub fn table(&self, name: &str) -> &[HighScore] {
+    self.tables.get(name).map(Vec::as_slice).unwrap_or_default()
+  }
+
+  // MARK: lengths()
+  /// The game lengths that have tables, by name.
+  pub fn lengths(&self) -> impl Iterator<Item = &str> {
+    self
+      .tables
+      .keys()
+      .filter_map(|name| name.split(SEPARATOR).next())
+  }
+
+  // MARK: qualifies()
+  pub fn qualifies(&self, length: GameLength, ruleset: &Ruleset, score: i64) -> bool {
+    let table = self.get(length, ruleset);
+    table.len() < MAX_SCORES || table.iter().any(|entry| score > entry.score)
+  }
+
+  // MARK: add()
+  /// Inserts a score into the table for its length and rules, returning its
+  /// 1-based rank if it made the cut.
+  pub fn add(&mut self, length: GameLength, entry: HighScore) -> Option<usize> {
+    let ruleset = &entry.ruleset;
+    let table = table_name(
+      &length.to_string(),
+      ruleset.difficulty,
+      ruleset.advisor_enabled,
+    );
+    self.insert(table, entry)
+  }
+
+  fn insert(&mut self, table: String, entry: HighScore) -> Option<usize> {
+    let table = self.tables.entry(table).or_default();
+    let rank = table
+      .iter()
+      .position(|existing| entry.score > existing.score)
+      .unwrap_or(table.len());
+    if rank >= MAX_SCORES {
+      return None;
+    }
+    table.insert(rank, entry);
+    table.truncate(MAX_SCORES);
+    Some(rank + 1)
+  }
+}
@@ -1,4 +1,5 @@
 use crate::drugs::Drug;
+use crate::money::Money;
 use std::collections::HashMap;
 use std::fmt;
 
@@ -31,7 +32,7 @@ impl Inventory {
   }
 
   // MARK: add()
-  pub fn add(&mut self, drug: Drug, amount: u32, cost: u32) {
+  pub fn add(&mut self, drug: Drug, amount: u32, cost: Money) {
     self
       .items
       .entry(drug)
@@ -60,20 +61,49 @@ impl Inventory {
   }
 
   // MARK: get_cost()
-  pub fn get_cost(&self, drug: Drug) -> Option<u32> {
+  pub fn get_cost(&self, drug: Drug) -> Option<Money> {
     self.items.get(&drug).map(|(_, cost)| cost.get())
   }
 
   // MARK: reset_cost()
   pub fn reset_cost(&mut self, drug: Drug) {
     if let Some((_, cost)) = self.items.get_mut(&drug) {
-      cost.0 = 0;
+      cost.0 = Money::ZERO;
+    }
+  }
+
+  // MARK: holding()
+  /// The amount and cost basis held of a drug, if it has ever been bought.
+  pub fn holding(&self, drug: Drug) -> Option<(u32, Money)> {
+    self
+      .items
+      .get(&drug)
+      .map(|(amt, cost)| (amt.get(), cost.get()))
+  }
+
+  // MARK: restore()
+  /// Puts back a holding exactly as `holding()` reported it.
+  pub fn restore(&mut self, drug: Drug, holding: Option<(u32, Money)>) {
+    match holding {
+      Some((amount, cost)) => {
+        self
+          .items
+          .insert(drug, (Amount::new(amount), Cost::new(cost)));
+      }
+      None => {
+        self.items.remove(&drug);
+      }
     }
   }
 
   pub fn has_items(&self) -> bool {
     self.items.values().any(|(amount, _)| amount.get() > 0)
   }
+
+  // MARK: total_amount()
+  pub fn total_amount(&self) -> u32 {
+    self.items.values().map(|(amount, _)| amount.get()).sum()
+  }
 }
 
 // MARK: - Amount type wrapper
@@ -111,20 +141,20 @@ impl fmt::Display for Amount {
 
 // MARK: - Cost type wrapper
 #[derive(Debug, Clone, Copy)]
-pub struct Cost(u32);
+pub struct Cost(Money);
 
 impl Cost {
-  pub fn new(value: u32) -> Self {
+  pub fn new(value: Money) -> Self {
     Self(value)
   }
 
-  pub fn get(&self) -> u32 {
+  pub fn get(&self) -> Money {
     self.0
   }
 }
 
 impl fmt::Display for Cost {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(f, "${}", self.0)
+    write!(f, "{}", self.0)
   }
 }
@@ -0,0 +1,31 @@
+//! The Dank Bytes engine. The game itself lives in `main.rs`; this also lets
+//! other programs drive the engine headlessly, e.g. through [`env::Env`].
+
+pub mod achievements;
+mod advisor;
+mod autopilot;
+mod basket;
+pub mod bus;
+pub mod drugs;
+pub mod env;
+mod equipment;
+pub mod events;
+pub mod game;
+mod heat;
+pub mod highscores;
+mod inventory;
+pub mod locations;
+mod market;
+pub mod money;
+mod networth;
+pub mod replay;
+pub mod ruleset;
+mod rumours;
+mod scenarios;
+pub mod serve;
+pub mod stats;
+mod storage;
+pub mod trade;
+mod tutorial;
+mod ui;
+mod undo;
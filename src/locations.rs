@@ -1,5 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::EventType;
+
 // MARK: Location enum
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Location {
   #[default]
   Fairfield,
@@ -23,3 +27,64 @@ impl std::fmt::Display for Location {
     }
   }
 }
+
+// MARK: get_location_list()
+pub fn get_location_list() -> [Location; 6] {
+  [
+    Location::Fairfield,
+    Location::Oakwood,
+    Location::Lakeview,
+    Location::Highland,
+    Location::Edgewater,
+    Location::Centerville,
+  ]
+}
+
+// MARK: - EventTable struct
+/// Odds of an event firing on arrival, and the relative weight of each event type.
+pub struct EventTable {
+  pub chance: f32,
+  pub weights: Vec<(EventType, f32)>,
+}
+
+impl Location {
+  // MARK: neighbours()
+  /// Locations next to this one on the travel grid, which hear its news first.
+  pub fn neighbours(&self) -> &'static [Location] {
+    match self {
+      Location::Fairfield => &[Location::Oakwood, Location::Highland],
+      Location::Oakwood => &[Location::Fairfield, Location::Lakeview, Location::Edgewater],
+      Location::Lakeview => &[Location::Oakwood, Location::Centerville],
+      Location::Highland => &[Location::Fairfield, Location::Edgewater],
+      Location::Edgewater => &[Location::Oakwood, Location::Highland, Location::Centerville],
+      Location::Centerville => &[Location::Lakeview, Location::Edgewater],
+    }
+  }
+
+  // MARK: event_table()
+  /// `event_chance` is the ruleset's base chance, which some locations raise.
+  pub fn event_table(&self, event_chance: f32) -> EventTable {
+    let weight = |e_type: &EventType| match (self, e_type) {
+      (Location::Highland, EventType::Mugging) => 3.0,
+      (Location::Edgewater, EventType::DrugShipment) => 3.0,
+      (Location::Edgewater, EventType::WarehouseRaid) => 1.0,
+      (_, EventType::WarehouseRaid) => 0.0,
+      (Location::Centerville, EventType::DrugBust) => 2.0,
+      (Location::Centerville, EventType::PoliceStop) => 3.0,
+      _ => 1.0,
+    };
+    let chance = match self {
+      Location::Centerville => event_chance + 0.05,
+      _ => event_chance,
+    };
+
+    EventTable {
+      chance,
+      weights: EventType::ALL
+        .iter()
+        .map(|e_type| (e_type.clone(), weight(e_type)))
+        .filter(|(_, weight)| *weight > 0.0)
+        .collect(),
+    }
+  }
+}
@@ -3,17 +3,32 @@ use eframe::egui::{self, ViewportBuilder};
 use eframe::{Error, NativeOptions, Result};
 use hello_egui::material_icons;
 
-mod drugs;
-mod events;
-mod game;
-mod inventory;
-mod locations;
-mod ui;
+use dankbytes::{achievements, game, highscores, replay, serve, stats};
 
 const LEAF_PNG: &[u8] = include_bytes!("../assets/leaf.png");
 
 fn main() -> Result<(), Error> {
-  let game = game::Game::new();
+  // headless check of a daily challenge result code: `dankbytes --verify <code>`
+  let args: Vec<String> = std::env::args().collect();
+  if let [_, flag, code] = &args[..]
+    && flag == "--verify"
+  {
+    attach_console();
+    std::process::exit(verify(code));
+  }
+  // JSON-RPC 2.0 over stdio for external bots: `dankbytes --serve-stdio`
+  if let [_, flag] = &args[..]
+    && flag == "--serve-stdio"
+  {
+    let served = serve::serve(std::io::stdin().lock(), std::io::stdout().lock());
+    std::process::exit(if served.is_ok() { 0 } else { 1 });
+  }
+
+  let mut game = game::Game::new();
+  game.bus.listen();
+  game.high_scores = highscores::HighScores::load();
+  game.career = stats::Stats::load();
+  game.achievements = achievements::Achievements::load();
   let ico = eframe::icon_data::from_png_bytes(LEAF_PNG).expect("Failed to load icon");
 
   eframe::run_native(
@@ -36,3 +51,42 @@ fn main() -> Result<(), Error> {
 
   Ok(())
 }
+
+// MARK: attach_console()
+/// Release builds on Windows have no console of their own, so borrow the one
+/// `--verify` was run from, or its output goes nowhere.
+#[cfg(windows)]
+fn attach_console() {
+  const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+  #[link(name = "kernel32")]
+  unsafe extern "system" {
+    fn AttachConsole(process_id: u32) -> i32;
+  }
+  // failing just means there's no console to print to, e.g. when piped
+  unsafe {
+    AttachConsole(ATTACH_PARENT_PROCESS);
+  }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
+// MARK: verify()
+fn verify(code: &str) -> i32 {
+  let verified = code
+    .parse::<replay::ResultCode>()
+    .and_then(|code| code.verify().map(|score| (code, score)));
+  match verified {
+    Ok((code, score)) => {
+      println!(
+        "Verified: {} daily challenge, {}, score {}",
+        code.date, code.length, score
+      );
+      0
+    }
+    Err(err) => {
+      eprintln!("Verification failed: {}", err);
+      1
+    }
+  }
+}
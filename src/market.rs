@@ -0,0 +1,147 @@
+use rand::Rng;
+
+use crate::drugs::{Drug, PriceRanges, get_rand_prices};
+use crate::events::{Event, EventType};
+use crate::locations::Location;
+
+// MARK: market constants
+const SHOCK_DECAY: f32 = 0.35; // fraction of a shock's effect lost each day
+const SPREAD_STRENGTH: f32 = 0.6; // fraction of a shock's effect that reaches neighbours
+const MIN_EFFECT: f32 = 0.05; // shocks weaker than this are dropped
+
+// MARK: - MarketShock struct
+/// A lasting price effect on one drug at one location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketShock {
+  pub drug: Drug,
+  pub location: Location,
+  pub multiplier: f32,
+  pub days_left: u32,
+  pub spreads: bool,
+}
+
+impl MarketShock {
+  // MARK: MarketShock::from_event()
+  pub fn from_event(event: &Event, location: Location) -> Vec<Self> {
+    let (multiplier, days) = match event.e_type {
+      EventType::DrugBust => (1.5, 4),
+      EventType::DrugShipment => (0.5, 3),
+      EventType::WarehouseRaid => (2.0, 5),
+      _ => return Vec::new(),
+    };
+    event
+      .e_drugs
+      .iter()
+      .map(|&drug| Self {
+        drug,
+        location,
+        multiplier,
+        days_left: days,
+        spreads: true,
+      })
+      .collect()
+  }
+
+  fn effect(&self) -> f32 {
+    (self.multiplier - 1.0).abs()
+  }
+
+  fn decay(&mut self) {
+    self.multiplier = 1.0 + (self.multiplier - 1.0) * (1.0 - SHOCK_DECAY);
+    self.days_left = self.days_left.saturating_sub(1);
+  }
+}
+
+// MARK: - Market struct
+#[derive(Debug, Default, Clone)]
+pub struct Market {
+  shocks: Vec<MarketShock>,
+}
+
+impl Market {
+  // MARK: add_shocks()
+  pub fn add_shocks(&mut self, shocks: Vec<MarketShock>) {
+    for shock in shocks {
+      self
+        .shocks
+        .retain(|s| s.drug != shock.drug || s.location != shock.location);
+      self.shocks.push(shock);
+    }
+  }
+
+  // MARK: shock_at()
+  pub fn shock_at(&self, location: Location, drug: Drug) -> Option<&MarketShock> {
+    self
+      .shocks
+      .iter()
+      .find(|s| s.location == location && s.drug == drug)
+  }
+
+  // MARK: prices_at()
+  /// Fresh prices for a location with any lingering shocks applied.
+  pub fn prices_at(
+    &self,
+    location: Location,
+    price_ranges: &PriceRanges,
+    rng: &mut impl Rng,
+  ) -> [u32; 7] {
+    let mut prices = get_rand_prices(price_ranges, rng);
+    for shock in self.shocks.iter().filter(|s| s.location == location) {
+      let price = &mut prices[shock.drug.as_index()];
+      *price = ((*price as f32 * shock.multiplier) as u32).max(1);
+    }
+    prices
+  }
+
+  // MARK: evolve_prices()
+  /// Moves yesterday's prices at a location halfway towards a fresh roll.
+  pub fn evolve_prices(
+    &self,
+    location: Location,
+    prices: &[u32; 7],
+    price_ranges: &PriceRanges,
+    rng: &mut impl Rng,
+  ) -> [u32; 7] {
+    let fresh = self.prices_at(location, price_ranges, rng);
+    let mut evolved = [0; 7];
+    for (i, price) in evolved.iter_mut().enumerate() {
+      *price = prices[i].midpoint(fresh[i]).max(1);
+    }
+    evolved
+  }
+
+  // MARK: tick()
+  /// Advances the market one day: shocks fade, and fresh ones spread to neighbours.
+  pub fn tick(&mut self) {
+    let mut spread = Vec::new();
+    for shock in self.shocks.iter_mut() {
+      shock.decay();
+      if shock.spreads {
+        shock.spreads = false;
+        let multiplier = 1.0 + (shock.multiplier - 1.0) * SPREAD_STRENGTH;
+        for &location in shock.location.neighbours() {
+          spread.push(MarketShock {
+            drug: shock.drug,
+            location,
+            multiplier,
+            days_left: shock.days_left,
+            spreads: (multiplier - 1.0).abs() * SPREAD_STRENGTH >= MIN_EFFECT,
+          });
+        }
+      }
+    }
+    self
+      .shocks
+      .retain(|s| s.days_left > 0 && s.effect() >= MIN_EFFECT);
+
+    // news never overrides what's already happening in a neighbourhood
+    for shock in spread {
+      if shock.days_left > 0
+        && shock.effect() >= MIN_EFFECT
+        && self.shock_at(shock.location, shock.drug).is_none()
+      {
+        self.shocks.push(shock);
+      }
+    }
+  }
+}
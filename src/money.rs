@@ -0,0 +1,161 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use eframe::emath::Numeric;
+use serde::{Deserialize, Serialize};
+use thousands::Separable;
+
+// MARK: Money struct
+/// A whole-dollar amount that can't overflow.
+///
+/// `+` and `-` saturate, so a long game tops out instead of panicking or wrapping.
+/// Use `checked_sub` where coming up short has to be handled.
+#[derive(
+  Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Money(u64);
+
+impl Money {
+  pub const ZERO: Money = Money(0);
+
+  // MARK: Money::new()
+  pub const fn new(dollars: u64) -> Self {
+    Self(dollars)
+  }
+
+  // MARK: get()
+  pub fn get(&self) -> u64 {
+    self.0
+  }
+
+  // MARK: is_zero()
+  pub fn is_zero(&self) -> bool {
+    self.0 == 0
+  }
+
+  // MARK: checked_sub()
+  pub fn checked_sub(self, other: Money) -> Option<Money> {
+    self.0.checked_sub(other.0).map(Money)
+  }
+
+  // MARK: times()
+  /// The cost of `units` items at this price each.
+  pub fn times(self, units: u32) -> Money {
+    Money(self.0.saturating_mul(units as u64))
+  }
+
+  // MARK: quantity_at()
+  /// How many items at `price` each this amount can pay for.
+  pub fn quantity_at(self, price: Money) -> u32 {
+    (self.0 / price.0.max(1)).min(u32::MAX as u64) as u32
+  }
+
+  // MARK: scale()
+  /// This amount multiplied by a rate, rounded down.
+  pub fn scale(self, factor: f32) -> Money {
+    Money((self.0 as f64 * factor as f64) as u64)
+  }
+
+  // MARK: signed()
+  /// The amount as an `i64`, for sums that can go negative.
+  pub fn signed(self) -> i64 {
+    i64::try_from(self.0).unwrap_or(i64::MAX)
+  }
+}
+
+impl From<u32> for Money {
+  fn from(dollars: u32) -> Self {
+    Money(dollars as u64)
+  }
+}
+
+// MARK: arithmetic impls
+impl Add for Money {
+  type Output = Money;
+
+  fn add(self, other: Money) -> Money {
+    Money(self.0.saturating_add(other.0))
+  }
+}
+
+impl AddAssign for Money {
+  fn add_assign(&mut self, other: Money) {
+    *self = *self + other;
+  }
+}
+
+impl Sub for Money {
+  type Output = Money;
+
+  fn sub(self, other: Money) -> Money {
+    Money(self.0.saturating_sub(other.0))
+  }
+}
+
+impl SubAssign for Money {
+  fn sub_assign(&mut self, other: Money) {
+    *self = *self - other;
+  }
+}
+
+impl Sum for Money {
+  fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+    iter.fold(Money::ZERO, Add::add)
+  }
+}
+
+// MARK: Numeric impl
+/// Lets sliders and drag values edit amounts directly.
+impl Numeric for Money {
+  const INTEGRAL: bool = true;
+  const MIN: Self = Money::ZERO;
+  const MAX: Self = Money(u64::MAX);
+
+  fn to_f64(self) -> f64 {
+    self.0 as f64
+  }
+
+  fn from_f64(num: f64) -> Self {
+    Money(num as u64)
+  }
+}
+
+// MARK: Money display
+impl fmt::Display for Money {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "${}", self.0.separate_with_commas())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn times_saturates_at_the_limit() {
+    assert_eq!(Money::new(3).times(4), Money::new(12));
+    assert_eq!(Money::new(3).times(0), Money::ZERO);
+    assert_eq!(
+      Money::from(u32::MAX).times(u32::MAX).get(),
+      u32::MAX as u64 * u32::MAX as u64
+    );
+    assert_eq!(Money::new(u64::MAX).times(2), Money::new(u64::MAX));
+  }
+
+  #[test]
+  fn quantity_at_rounds_down_and_stays_in_range() {
+    assert_eq!(Money::new(10).quantity_at(Money::new(3)), 3);
+    assert_eq!(Money::new(2).quantity_at(Money::new(3)), 0);
+    assert_eq!(Money::new(5).quantity_at(Money::ZERO), 5);
+    assert_eq!(Money::new(u64::MAX).quantity_at(Money::new(1)), u32::MAX);
+  }
+
+  #[test]
+  fn sums_saturate() {
+    assert_eq!(Money::new(u64::MAX) + Money::new(1), Money::new(u64::MAX));
+    assert_eq!(Money::new(1) - Money::new(2), Money::ZERO);
+    assert_eq!(Money::new(1).checked_sub(Money::new(2)), None);
+  }
+}
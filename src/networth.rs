@@ -0,0 +1,59 @@
+use crate::drugs::{get_drug_list, get_drug_price};
+use crate::game::Game;
+use crate::money::Money;
+
+// MARK: Valuation enum
+/// Which prices held product is valued at.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Valuation {
+  /// Today's prices at the player's location.
+  #[default]
+  Current,
+  /// The middle of each drug's usual price range.
+  Average,
+}
+
+// MARK: - NetWorth struct
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NetWorth {
+  pub cash: Money,
+  pub inventory: Money,
+  pub equipment: Money,
+  pub debt: Money,
+}
+
+impl NetWorth {
+  // MARK: NetWorth::of()
+  pub fn of(game: &Game, valuation: Valuation) -> Self {
+    let inventory = get_drug_list()
+      .iter()
+      .map(|&drug| {
+        let price = match valuation {
+          Valuation::Current => get_drug_price(drug, &game.prices),
+          Valuation::Average => {
+            let (min, max) = game.ruleset.price_ranges[drug.as_index()];
+            min.midpoint(max)
+          }
+        };
+        Money::from(price).times(game.inventory.get_amount(drug).unwrap_or(0))
+      })
+      .sum();
+
+    Self {
+      cash: game.cash,
+      inventory,
+      equipment: game.equipment.resale_value(),
+      debt: game.debt,
+    }
+  }
+
+  // MARK: assets()
+  pub fn assets(&self) -> Money {
+    self.cash + self.inventory + self.equipment
+  }
+
+  // MARK: total()
+  pub fn total(&self) -> i64 {
+    self.assets().signed() - self.debt.signed()
+  }
+}
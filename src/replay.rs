@@ -0,0 +1,368 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::drugs::{Drug, get_drug_list};
+use crate::equipment::{Equipment, get_equipment_list};
+use crate::game::{Game, GameLength};
+use crate::locations::{Location, get_location_list};
+use crate::money::Money;
+use crate::trade::TradeError;
+
+// MARK: replay constants
+const CODE_VERSION: &str = "DB2";
+
+// MARK: daily_seed()
+/// Seed shared by everyone playing the daily challenge on `date` (FNV-1a of the date).
+pub fn daily_seed(date: &str) -> u64 {
+  date.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+    (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+  })
+}
+
+// MARK: - Action enum
+/// A player input that changes the game, as recorded for replays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+  Buy(Drug, u32),
+  Sell(Drug, u32),
+  Travel(Location),
+  Wait,
+  RepayDebt(Money),
+  BuyEquipment(Equipment),
+  SellEquipment(Equipment),
+  AcceptOffer,
+  PayInformant,
+  Undo,
+  Retire,
+}
+
+impl Action {
+  // MARK: Action::apply()
+  /// Performs the action, passing on why a trade or repayment failed.
+  pub fn apply(self, game: &mut Game) -> Result<(), TradeError> {
+    match self {
+      Action::Buy(drug, amount) => game.buy(drug, amount).map(drop)?,
+      Action::Sell(drug, amount) => game.sell(drug, amount).map(drop)?,
+      Action::Travel(location) => game.travel(location),
+      Action::Wait => game.wait(),
+      Action::RepayDebt(amount) => game.repay_debt(amount).map(drop)?,
+      Action::BuyEquipment(item) => game.buy_equipment(item),
+      Action::SellEquipment(item) => game.sell_equipment(item)?,
+      Action::AcceptOffer => game.accept_offer(),
+      Action::PayInformant => game.pay_informant(),
+      Action::Undo => drop(game.undo()),
+      Action::Retire => game.retire(),
+    }
+    Ok(())
+  }
+}
+
+// MARK: Action display
+/// Writes the action as a short token, e.g. `b3.10` to buy 10 of the fourth drug.
+impl fmt::Display for Action {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Action::Buy(drug, amount) => write!(f, "b{}.{}", drug.as_index(), amount),
+      Action::Sell(drug, amount) => write!(f, "s{}.{}", drug.as_index(), amount),
+      Action::Travel(location) => write!(f, "t{}", index_of(&get_location_list(), *location)),
+      Action::Wait => write!(f, "w"),
+      Action::RepayDebt(amount) => write!(f, "r{}", amount.get()),
+      Action::BuyEquipment(item) => write!(f, "e{}", index_of(&get_equipment_list(), *item)),
+      Action::SellEquipment(item) => write!(f, "x{}", index_of(&get_equipment_list(), *item)),
+      Action::AcceptOffer => write!(f, "a"),
+      Action::PayInformant => write!(f, "i"),
+      Action::Undo => write!(f, "u"),
+      Action::Retire => write!(f, "q"),
+    }
+  }
+}
+
+// MARK: Action parsing
+impl FromStr for Action {
+  type Err = ReplayError;
+
+  fn from_str(token: &str) -> Result<Self, Self::Err> {
+    let malformed = || ReplayError::Malformed(format!("bad action `{}`", token));
+    let (kind, args) = token.split_at_checked(1).ok_or_else(malformed)?;
+    let number = |s: &str| s.parse::<u32>().map_err(|_| malformed());
+    let drug_amount = |s: &str| -> Result<(Drug, u32), ReplayError> {
+      let (drug, amount) = s.split_once('.').ok_or_else(malformed)?;
+      let drug = nth(&get_drug_list(), number(drug)?).ok_or_else(malformed)?;
+      Ok((drug, number(amount)?))
+    };
+    let item = |s: &str| nth(&get_equipment_list(), number(s)?).ok_or_else(malformed);
+
+    match kind {
+      "b" => drug_amount(args).map(|(drug, amount)| Action::Buy(drug, amount)),
+      "s" => drug_amount(args).map(|(drug, amount)| Action::Sell(drug, amount)),
+      "t" => nth(&get_location_list(), number(args)?)
+        .map(Action::Travel)
+        .ok_or_else(malformed),
+      "r" => args
+        .parse()
+        .map(|amount| Action::RepayDebt(Money::new(amount)))
+        .map_err(|_| malformed()),
+      "e" => item(args).map(Action::BuyEquipment),
+      "x" => item(args).map(Action::SellEquipment),
+      "w" if args.is_empty() => Ok(Action::Wait),
+      "a" if args.is_empty() => Ok(Action::AcceptOffer),
+      "i" if args.is_empty() => Ok(Action::PayInformant),
+      "u" if args.is_empty() => Ok(Action::Undo),
+      "q" if args.is_empty() => Ok(Action::Retire),
+      _ => Err(malformed()),
+    }
+  }
+}
+
+// MARK: index helpers
+fn index_of<T: PartialEq>(list: &[T], item: T) -> usize {
+  list.iter().position(|x| *x == item).unwrap_or_default()
+}
+
+fn nth<T: Copy>(list: &[T], index: u32) -> Option<T> {
+  list.get(index as usize).copied()
+}
+
+// MARK: - ReplayError enum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+  Malformed(String),
+  Unfinished,
+  WrongSeed {
+    date: String,
+  },
+  ScoreMismatch {
+    claimed: i64,
+    actual: i64,
+  },
+  /// More actions were recorded after the game had already ended.
+  PlayedOn,
+}
+
+impl fmt::Display for ReplayError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ReplayError::Malformed(reason) => write!(f, "malformed result code: {}", reason),
+      ReplayError::Unfinished => write!(f, "the recorded actions don't finish the game"),
+      ReplayError::WrongSeed { date } => {
+        write!(f, "the seed isn't the daily challenge seed for {}", date)
+      }
+      ReplayError::PlayedOn => write!(f, "actions were recorded after the game ended"),
+      ReplayError::ScoreMismatch { claimed, actual } => {
+        write!(
+          f,
+          "claimed a score of {} but the replay scores {}",
+          claimed, actual
+        )
+      }
+    }
+  }
+}
+
+// MARK: - ResultCode struct
+/// Everything needed to replay a daily challenge run and check its score.
+///
+/// Written as `DB2:<date>:<seed hex>:<length>:<score>:<actions>`, with actions comma separated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultCode {
+  /// The day of the challenge, which the seed has to match.
+  pub date: String,
+  pub seed: u64,
+  pub length: GameLength,
+  pub score: i64,
+  pub actions: Vec<Action>,
+}
+
+impl ResultCode {
+  // MARK: ResultCode::of()
+  pub fn of(game: &Game) -> Self {
+    Self {
+      date: game.daily_date.clone(),
+      seed: game.seed,
+      length: game.game_length,
+      score: game.score(),
+      actions: game.actions.clone(),
+    }
+  }
+
+  // MARK: replay()
+  /// Plays the recorded actions through a fresh game of the code's daily challenge,
+  /// refusing codes that carry on past the end of the game.
+  pub fn replay(&self) -> Result<Game, ReplayError> {
+    let mut game = Game::with_seed(self.seed);
+    game.game_length = self.length;
+    game.start_daily_on(&self.date);
+    for &action in &self.actions {
+      if game.game_over {
+        return Err(ReplayError::PlayedOn);
+      }
+      // failed trades were recorded too, and fail the same way again
+      action.apply(&mut game).ok();
+    }
+    Ok(game)
+  }
+
+  // MARK: verify()
+  /// Checks the run was that day's challenge and replays it headlessly,
+  /// returning the score if it matches the claim.
+  pub fn verify(&self) -> Result<i64, ReplayError> {
+    if self.seed != daily_seed(&self.date) {
+      return Err(ReplayError::WrongSeed {
+        date: self.date.clone(),
+      });
+    }
+    let game = self.replay()?;
+    if !game.game_over {
+      return Err(ReplayError::Unfinished);
+    }
+    match game.score() {
+      actual if actual == self.score => Ok(actual),
+      actual => Err(ReplayError::ScoreMismatch {
+        claimed: self.score,
+        actual,
+      }),
+    }
+  }
+}
+
+// MARK: ResultCode display
+impl fmt::Display for ResultCode {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let length = match self.length {
+      GameLength::Short => "s".to_string(),
+      GameLength::Medium => "m".to_string(),
+      GameLength::Long => "l".to_string(),
+      GameLength::Custom(days) => format!("c{}", days),
+      GameLength::Endless => "e".to_string(),
+    };
+    let actions: Vec<String> = self.actions.iter().map(Action::to_string).collect();
+    write!(
+      f,
+      "{}:{}:{:x}:{}:{}:{}",
+      CODE_VERSION,
+      self.date,
+      self.seed,
+      length,
+      self.score,
+      actions.join(",")
+    )
+  }
+}
+
+// MARK: ResultCode parsing
+impl FromStr for ResultCode {
+  type Err = ReplayError;
+
+  fn from_str(code: &str) -> Result<Self, Self::Err> {
+    let malformed = |reason: &str| ReplayError::Malformed(reason.to_string());
+    let parts: Vec<&str> = code.trim().split(':').collect();
+    let [version, date, seed, length, score, actions] = parts[..] else {
+      return Err(malformed("expected six `:` separated parts"));
+    };
+    if version != CODE_VERSION {
+      return Err(malformed("unknown version"));
+    }
+    if date.is_empty() {
+      return Err(malformed("missing date"));
+    }
+
+    let seed = u64::from_str_radix(seed, 16).map_err(|_| malformed("bad seed"))?;
+    let length = match length {
+      "s" => GameLength::Short,
+      "m" => GameLength::Medium,
+      "l" => GameLength::Long,
+      "e" => GameLength::Endless,
+      custom => custom
+        .strip_prefix('c')
+        .and_then(|days| days.parse().ok())
+        .map(GameLength::Custom)
+        .ok_or_else(|| malformed("bad game length"))?,
+    };
+    let score = score.parse().map_err(|_| malformed("bad score"))?;
+    let actions = actions
+      .split(',')
+      .filter(|token| !token.is_empty())
+      .map(str::parse)
+      .collect::<Result<_, _>>()?;
+
+    Ok(Self {
+      date: date.to_string(),
+      seed,
+      length,
+      score,
+      actions,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const DATE: &str = "2026-10-19";
+
+  /// Plays a whole daily challenge, trading a little each day so the code has some variety.
+  fn play_daily() -> Game {
+    let mut game = Game::with_seed(0);
+    game.game_length = GameLength::Custom(20);
+    game.start_daily_on(DATE);
+    let locations = get_location_list();
+    let drugs = get_drug_list();
+    while !game.game_over {
+      let day = game.day as usize;
+      game.sell_all(drugs[day % drugs.len()]).ok();
+      game.buy(drugs[(day + 1) % drugs.len()], 5).ok();
+      game.repay_debt(Money::new(100)).ok();
+      game.travel(locations[(day + 1) % locations.len()]);
+    }
+    game
+  }
+
+  #[test]
+  fn code_round_trips_and_verifies() {
+    let game = play_daily();
+    let code = ResultCode::of(&game);
+    let parsed: ResultCode = code.to_string().parse().unwrap();
+    assert_eq!(parsed, code);
+    assert_eq!(parsed.verify(), Ok(game.score()));
+  }
+
+  #[test]
+  fn rejects_a_seed_from_another_day() {
+    let mut code = ResultCode::of(&play_daily());
+    code.seed = daily_seed("2026-10-18");
+    assert!(matches!(code.verify(), Err(ReplayError::WrongSeed { .. })));
+  }
+
+  #[test]
+  fn rejects_a_false_score() {
+    let mut code = ResultCode::of(&play_daily());
+    code.score += 1;
+    assert!(matches!(
+      code.verify(),
+      Err(ReplayError::ScoreMismatch { .. })
+    ));
+  }
+
+  #[test]
+  fn rejects_unfinished_runs() {
+    let mut code = ResultCode::of(&play_daily());
+    code.actions.truncate(code.actions.len() / 2);
+    assert_eq!(code.verify(), Err(ReplayError::Unfinished));
+  }
+
+  #[test]
+  fn rejects_actions_after_the_end() {
+    let mut game = play_daily();
+    let day = game.day;
+    game.travel(get_location_list()[1]);
+    game.wait();
+    assert_eq!(game.day, day, "a finished game can't be played on");
+    let mut code = ResultCode::of(&game);
+    let locations = get_location_list();
+    code
+      .actions
+      .extend([Action::Travel(locations[1]), Action::Travel(locations[2])]);
+    assert_eq!(code.verify(), Err(ReplayError::PlayedOn));
+  }
+}
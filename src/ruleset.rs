@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+
+use crate::drugs::{PRICE_RANGES, PriceRanges, get_drug_list};
+use crate::money::Money;
+
+// MARK: ruleset constants
+pub const MAX_STARTING_MONEY: Money = Money::new(1_000_000);
+/// Highest price a custom ruleset may give a drug.
+pub const MAX_PRICE: u32 = 10_000;
+
+// MARK: Difficulty enum
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+  Easy,
+  #[default]
+  Normal,
+  Hard,
+  Custom,
+}
+
+// MARK: difficulty display
+impl std::fmt::Display for Difficulty {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Difficulty::Easy => write!(f, "Easy"),
+      Difficulty::Normal => write!(f, "Normal"),
+      Difficulty::Hard => write!(f, "Hard"),
+      Difficulty::Custom => write!(f, "Custom"),
+    }
+  }
+}
+
+// MARK: get_difficulty_list()
+pub fn get_difficulty_list() -> [Difficulty; 4] {
+  [
+    Difficulty::Easy,
+    Difficulty::Normal,
+    Difficulty::Hard,
+    Difficulty::Custom,
+  ]
+}
+
+// MARK: - Ruleset struct
+/// The numbers a game is played by, chosen before it starts.
+/// Missing fields deserialize from the normal preset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ruleset {
+  pub difficulty: Difficulty,
+  pub starting_cash: Money,
+  pub starting_debt: Money,
+  pub interest_rate: f32,
+  pub event_chance: f32,
+  /// Most of the player's cash a mugger can take, as a fraction.
+  pub mugging_cash_ratio: f32,
+  pub price_ranges: PriceRanges,
+  /// Whether the trade advisor gives hints. Off for competitive play.
+  pub advisor_enabled: bool,
+}
+
+impl Default for Ruleset {
+  fn default() -> Self {
+    Self::preset(Difficulty::Normal)
+  }
+}
+
+impl Ruleset {
+  // MARK: Ruleset::competitive()
+  /// The normal rules with no hints, so every player is on equal footing.
+  pub fn competitive() -> Self {
+    Self {
+      advisor_enabled: false,
+      ..Self::default()
+    }
+  }
+
+  // MARK: Ruleset::preset()
+  pub fn preset(difficulty: Difficulty) -> Self {
+    let normal = Self {
+      difficulty,
+      starting_cash: Money::new(2000),
+      starting_debt: Money::new(2000),
+      interest_rate: 0.09,
+      event_chance: 0.15,
+      mugging_cash_ratio: 0.25,
+      price_ranges: PRICE_RANGES,
+      advisor_enabled: true,
+    };
+    match difficulty {
+      Difficulty::Easy => Self {
+        starting_cash: Money::new(5000),
+        starting_debt: Money::new(1000),
+        interest_rate: 0.05,
+        event_chance: 0.12,
+        mugging_cash_ratio: 0.15,
+        ..normal
+      },
+      Difficulty::Hard => Self {
+        starting_cash: Money::new(1000),
+        starting_debt: Money::new(4000),
+        interest_rate: 0.12,
+        event_chance: 0.2,
+        mugging_cash_ratio: 0.4,
+        ..normal
+      },
+      Difficulty::Normal | Difficulty::Custom => normal,
+    }
+  }
+
+  // MARK: Ruleset::validate()
+  /// Checks rules that came from outside the game, e.g. over `--serve-stdio`,
+  /// describing the first thing that would break a game played by them.
+  pub fn validate(&self) -> Result<(), String> {
+    let money = [
+      ("starting_cash", self.starting_cash),
+      ("starting_debt", self.starting_debt),
+    ];
+    for (name, amount) in money {
+      if amount > MAX_STARTING_MONEY {
+        return Err(format!(
+          "{} can be at most {}, not {}.",
+          name, MAX_STARTING_MONEY, amount
+        ));
+      }
+    }
+    let rates = [
+      ("interest_rate", self.interest_rate),
+      ("event_chance", self.event_chance),
+      ("mugging_cash_ratio", self.mugging_cash_ratio),
+    ];
+    for (name, rate) in rates {
+      if !rate.is_finite() || !(0.0..=1.0).contains(&rate) {
+        return Err(format!("{} must be between 0 and 1, not {}.", name, rate));
+      }
+    }
+    for (drug, (min, max)) in get_drug_list().into_iter().zip(self.price_ranges) {
+      if min == 0 || min > max || max > MAX_PRICE {
+        return Err(format!(
+          "The price range for {} must run upwards within 1 to {}, not {} to {}.",
+          drug, MAX_PRICE, min, max
+        ));
+      }
+    }
+    Ok(())
+  }
+}
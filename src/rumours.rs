@@ -0,0 +1,89 @@
+use rand::Rng;
+
+use crate::drugs::{Drug, get_drug_list};
+use crate::events::EventType;
+use crate::locations::{Location, get_location_list};
+use crate::money::Money;
+
+// MARK: rumour constants
+pub const RUMOUR_RELIABILITY: f32 = 0.5;
+pub const INFORMANT_RELIABILITY: f32 = 0.9;
+pub const INFORMANT_FEE: Money = Money::new(500);
+const RUMOUR_LEAD_MIN: u32 = 1;
+const RUMOUR_LEAD_MAX: u32 = 3;
+
+// MARK: - Rumour struct
+/// A prediction that a market event will hit a location on a given day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rumour {
+  pub e_type: EventType,
+  pub drug: Drug,
+  pub location: Location,
+  pub day: u32,
+}
+
+impl Rumour {
+  // MARK: Rumour::describe()
+  pub fn describe(&self, today: u32) -> String {
+    let what = match self.e_type {
+      EventType::DrugBust => "a big bust on",
+      _ => "a shipment of",
+    };
+    let when = match self.day.saturating_sub(today) {
+      0 => "today".to_string(),
+      1 => "tomorrow".to_string(),
+      days => format!("in {} days", days),
+    };
+    format!(
+      "There's going to be {} {} in {} {}.",
+      what, self.drug, self.location, when
+    )
+  }
+}
+
+// MARK: - Rumours struct
+/// Rumours the player has heard, and the events the true ones have scheduled.
+#[derive(Debug, Default, Clone)]
+pub struct Rumours {
+  heard: Vec<Rumour>,
+  scheduled: Vec<Rumour>,
+}
+
+impl Rumours {
+  // MARK: hear()
+  /// Makes up a new rumour, scheduling its event with probability `reliability`.
+  pub fn hear(&mut self, today: u32, reliability: f32, rng: &mut impl Rng) -> Rumour {
+    let drugs = get_drug_list();
+    let locations = get_location_list();
+    let rumour = Rumour {
+      e_type: if rng.random::<bool>() {
+        EventType::DrugBust
+      } else {
+        EventType::DrugShipment
+      },
+      drug: drugs[rng.random_range(0..drugs.len())],
+      location: locations[rng.random_range(0..locations.len())],
+      day: today + rng.random_range(RUMOUR_LEAD_MIN..=RUMOUR_LEAD_MAX),
+    };
+
+    if rng.random::<f32>() < reliability {
+      self.scheduled.push(rumour.clone());
+    }
+    self.heard.push(rumour.clone());
+    rumour
+  }
+
+  // MARK: take_due()
+  /// Removes and returns the scheduled events happening on `today`.
+  pub fn take_due(&mut self, today: u32) -> Vec<Rumour> {
+    self.heard.retain(|r| r.day > today);
+    let (due, later) = self.scheduled.drain(..).partition(|r| r.day <= today);
+    self.scheduled = later;
+    due
+  }
+
+  // MARK: heard()
+  pub fn heard(&self) -> &[Rumour] {
+    &self.heard
+  }
+}
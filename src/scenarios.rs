@@ -0,0 +1,154 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use thousands::Separable;
+
+use crate::bus::{GameEvent, Subscriber};
+use crate::drugs::Drug;
+use crate::game::Game;
+use crate::locations::Location;
+use crate::money::Money;
+use crate::networth::Valuation;
+use crate::ruleset::Ruleset;
+use crate::trade::TradeReceipt;
+
+const SCENARIOS_JSON: &str = include_str!("../assets/scenarios.json");
+
+static SCENARIOS: LazyLock<Vec<Scenario>> =
+  LazyLock::new(|| serde_json::from_str(SCENARIOS_JSON).expect("Failed to parse scenarios"));
+
+// MARK: get_scenario_list()
+pub fn get_scenario_list() -> &'static [Scenario] {
+  &SCENARIOS
+}
+
+// MARK: Goal enum
+/// What the player has to do to win a scenario.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Goal {
+  /// Have at least `amount` cash on hand by `day`.
+  Cash { amount: Money, day: u32 },
+  /// Sell `amount` units of a drug before time runs out.
+  Sell { drug: Drug, amount: u32 },
+  /// Reach a net worth of at least `amount` before time runs out.
+  NetWorth { amount: i64 },
+  /// Pay off all debt by `day`.
+  DebtFree { day: u32 },
+}
+
+// MARK: Goal display
+impl std::fmt::Display for Goal {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Goal::Cash { amount, day } => write!(f, "Have {} cash by day {}", amount, day),
+      Goal::Sell { drug, amount } => {
+        write!(f, "Sell {} {}", amount.separate_with_commas(), drug)
+      }
+      Goal::NetWorth { amount } => {
+        write!(f, "Reach a net worth of ${}", amount.separate_with_commas())
+      }
+      Goal::DebtFree { day } => write!(f, "Pay off your debt by day {}", day),
+    }
+  }
+}
+
+// MARK: - Scenario struct
+/// A hand-made starting position with its own rules and win condition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+  pub name: String,
+  pub description: String,
+  pub goal: Goal,
+  pub days: u32,
+  pub cash: Money,
+  pub debt: Money,
+  #[serde(default)]
+  pub location: Location,
+  #[serde(default)]
+  pub inventory: Vec<(Drug, u32)>,
+  /// Fixed seed, for reproducing a specific run.
+  #[serde(default)]
+  pub seed: Option<u64>,
+  #[serde(default)]
+  pub ruleset: Ruleset,
+}
+
+// MARK: - Outcome enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+  Won,
+  Lost,
+}
+
+// MARK: - ScenarioRun struct
+/// A scenario being played, and how far the player is towards its goal.
+#[derive(Debug, Clone)]
+pub struct ScenarioRun {
+  pub scenario: Scenario,
+  pub outcome: Option<Outcome>,
+  sold: u32,
+}
+
+impl ScenarioRun {
+  // MARK: ScenarioRun::new()
+  pub fn new(scenario: Scenario) -> Self {
+    Self {
+      scenario,
+      outcome: None,
+      sold: 0,
+    }
+  }
+
+  // MARK: judge()
+  /// Whether the goal has been met or missed yet.
+  pub fn judge(&self, game: &Game) -> Option<Outcome> {
+    match self.scenario.goal {
+      Goal::Cash { amount, .. } if game.cash >= amount => Some(Outcome::Won),
+      Goal::Sell { amount, .. } if self.sold >= amount => Some(Outcome::Won),
+      Goal::NetWorth { amount } if game.net_worth(Valuation::Current).total() >= amount => {
+        Some(Outcome::Won)
+      }
+      Goal::DebtFree { .. } if game.debt.is_zero() => Some(Outcome::Won),
+      Goal::Cash { day, .. } | Goal::DebtFree { day } if game.day >= day => Some(Outcome::Lost),
+      _ => None,
+    }
+  }
+
+  // MARK: progress()
+  pub fn progress(&self, game: &Game) -> String {
+    match self.scenario.goal {
+      Goal::Cash { amount, .. } => format!("{} / {}", game.cash, amount),
+      Goal::Sell { drug, amount } => format!(
+        "{} / {} {} sold",
+        self.sold.separate_with_commas(),
+        amount.separate_with_commas(),
+        drug
+      ),
+      Goal::NetWorth { amount } => format!(
+        "${} / ${}",
+        game
+          .net_worth(Valuation::Current)
+          .total()
+          .separate_with_commas(),
+        amount.separate_with_commas()
+      ),
+      Goal::DebtFree { .. } => format!("{} left", game.debt),
+    }
+  }
+}
+
+// MARK: Subscriber impl
+impl Subscriber for ScenarioRun {
+  fn notify(&mut self, event: &GameEvent) {
+    let Goal::Sell { drug: goal, .. } = self.scenario.goal else {
+      return;
+    };
+    match event {
+      GameEvent::Sold { drug, amount, .. } if *drug == goal => self.sold += amount,
+      GameEvent::TradeUndone {
+        receipt: TradeReceipt::Sold { drug, amount, .. },
+      } if *drug == goal => self.sold = self.sold.saturating_sub(*amount),
+      _ => {}
+    }
+  }
+}
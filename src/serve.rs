@@ -0,0 +1,528 @@
+//! `dankbytes --serve-stdio`: the engine over JSON-RPC 2.0, for bots in any language.
+//!
+//! Messages are line-delimited: each request, notification or batch is one line,
+//! and so is each response. The client opens with the `new_game` handshake, e.g.
+//! `{"jsonrpc": "2.0", "id": 1, "method": "new_game", "params": {"seed": 42, "length": "Short"}}`;
+//! `ruleset` may be given too, and anything left out takes its default. After that
+//! it plays with named params, e.g. `"method": "buy", "params": {"drug": "Weed", "amount": 10}`.
+//! Every call's result is the game state afterwards. Errors use the standard codes,
+//! plus `-32000` for a move the rules don't allow and `-32001` before `new_game`.
+
+use std::io::{self, BufRead, Write};
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+
+use crate::drugs::{Drug, get_drug_list, get_drug_price};
+use crate::game::{Game, GameLength, MAX_CUSTOM_DAYS};
+use crate::locations::Location;
+use crate::money::Money;
+use crate::ruleset::Ruleset;
+
+// MARK: serve constants
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+/// A move the game's rules don't allow right now.
+const REFUSED: i32 = -32000;
+/// A move made before `new_game`.
+const NO_GAME: i32 = -32001;
+/// Methods that play a `Command`, as opposed to `new_game`.
+const COMMANDS: [&str; 9] = [
+  "travel",
+  "buy",
+  "sell",
+  "repay",
+  "wait",
+  "accept_offer",
+  "ack",
+  "retire",
+  "state",
+];
+
+// MARK: Handshake struct
+/// How the client wants the game set up.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Handshake {
+  pub seed: Option<u64>,
+  pub length: GameLength,
+  pub ruleset: Ruleset,
+}
+
+impl Handshake {
+  // MARK: Handshake::validate()
+  /// Turns away setups the engine can't play, before a game is started with them.
+  pub fn validate(&self) -> Result<(), String> {
+    if let GameLength::Custom(days) = self.length
+      && !(1..=MAX_CUSTOM_DAYS).contains(&days)
+    {
+      return Err(format!(
+        "A custom game must last 1 to {} days, not {}.",
+        MAX_CUSTOM_DAYS, days
+      ));
+    }
+    self.ruleset.validate()
+  }
+}
+
+// MARK: - Command enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum Command {
+  Travel {
+    to: Location,
+  },
+  Buy {
+    drug: Drug,
+    amount: u32,
+  },
+  Sell {
+    drug: Drug,
+    amount: u32,
+  },
+  Repay {
+    amount: Money,
+  },
+  Wait,
+  AcceptOffer,
+  /// Marks today's event as read, so it's left out of later states.
+  Ack,
+  /// Ends an endless game at today's prices.
+  Retire,
+  /// Resends the state without doing anything.
+  State,
+}
+
+// MARK: - Request struct
+#[derive(Debug, Deserialize)]
+struct Request {
+  jsonrpc: String,
+  method: String,
+  #[serde(default)]
+  params: Value,
+  /// Left out for a notification, which gets no response; `null` is still an id.
+  #[serde(default, deserialize_with = "present")]
+  id: Option<Value>,
+}
+
+fn present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+  Value::deserialize(deserializer).map(Some)
+}
+
+// MARK: - Response struct
+#[derive(Debug, Serialize)]
+struct Response {
+  jsonrpc: &'static str,
+  #[serde(flatten)]
+  payload: Payload,
+  id: Value,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Payload {
+  Result(State),
+  Error(RpcError),
+}
+
+impl Response {
+  // MARK: Response::new()
+  fn new(id: Value, outcome: Result<State, RpcError>) -> Self {
+    Self {
+      jsonrpc: "2.0",
+      payload: match outcome {
+        Ok(state) => Payload::Result(state),
+        Err(err) => Payload::Error(err),
+      },
+      id,
+    }
+  }
+}
+
+// MARK: - RpcError struct
+#[derive(Debug, Serialize)]
+struct RpcError {
+  code: i32,
+  message: String,
+}
+
+impl RpcError {
+  // MARK: RpcError::new()
+  fn new(code: i32, message: impl Into<String>) -> Self {
+    Self {
+      code,
+      message: message.into(),
+    }
+  }
+}
+
+// MARK: - Reply enum
+/// What goes back for one line: a response, or a batch of them.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Reply {
+  Single(Response),
+  Batch(Vec<Response>),
+}
+
+// MARK: - State struct
+/// Everything a client needs to decide its next command.
+#[derive(Debug, Serialize)]
+struct State {
+  seed: u64,
+  day: u32,
+  days_left: Option<u32>,
+  location: Location,
+  cash: Money,
+  debt: Money,
+  heat: u32,
+  free_space: u32,
+  market: Vec<Listing>,
+  event: Option<EventView>,
+  game_over: bool,
+  score: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+struct Listing {
+  drug: Drug,
+  price: u32,
+  held: u32,
+  /// What was last paid per unit, if any are held.
+  cost: Option<Money>,
+}
+
+#[derive(Debug, Serialize)]
+struct EventView {
+  message: String,
+  /// Equipment on offer and its price, taken with `accept_offer`.
+  offer: Option<(String, Money)>,
+}
+
+// MARK: - Server struct
+struct Server {
+  game: Game,
+  acked_day: Option<u32>,
+}
+
+impl Server {
+  // MARK: Server::new()
+  fn new(handshake: Handshake) -> Self {
+    let mut game = match handshake.seed {
+      Some(seed) => Game::with_seed(seed),
+      None => Game::new(),
+    };
+    game.game_length = handshake.length;
+    game.ruleset = handshake.ruleset;
+    game.start();
+    Self {
+      game,
+      acked_day: None,
+    }
+  }
+
+  // MARK: handle()
+  fn handle(&mut self, command: Command) -> Result<(), String> {
+    let game = &mut self.game;
+    if game.game_over && command != Command::State {
+      return Err("The game is over.".to_string());
+    }
+    match command {
+      Command::Travel { to } if to == game.location => {
+        return Err(format!("You're already in {}.", to));
+      }
+      Command::Travel { to } => game.travel(to),
+      Command::Buy { drug, amount } => drop(game.buy(drug, amount).map_err(|e| e.to_string())?),
+      Command::Sell { drug, amount } => drop(game.sell(drug, amount).map_err(|e| e.to_string())?),
+      Command::Repay { amount } => drop(game.repay_debt(amount).map_err(|e| e.to_string())?),
+      Command::Wait => game.wait(),
+      Command::AcceptOffer => {
+        let Some((_, price)) = game.event.as_ref().and_then(|event| event.e_offer) else {
+          return Err("Nobody is offering you anything.".to_string());
+        };
+        if game.cash < price {
+          return Err("You can't afford it.".to_string());
+        }
+        game.accept_offer();
+      }
+      Command::Ack => self.acked_day = Some(game.day),
+      Command::Retire if game.game_length != GameLength::Endless => {
+        return Err("You can only retire from an endless game.".to_string());
+      }
+      Command::Retire => game.retire(),
+      Command::State => {}
+    }
+    Ok(())
+  }
+
+  // MARK: state()
+  fn state(&self) -> State {
+    let game = &self.game;
+    let event = game
+      .event
+      .as_ref()
+      .filter(|_| self.acked_day != Some(game.day))
+      .map(|event| EventView {
+        message: event.e_msg.clone(),
+        offer: event.e_offer.map(|(item, price)| (item.to_string(), price)),
+      });
+    State {
+      seed: game.seed,
+      day: game.day,
+      days_left: game.days_left,
+      location: game.location,
+      cash: game.cash,
+      debt: game.debt,
+      heat: game.heat.get(),
+      free_space: game.free_space(),
+      market: get_drug_list()
+        .into_iter()
+        .map(|drug| Listing {
+          drug,
+          price: get_drug_price(drug, &game.prices),
+          held: game.inventory.get_amount(drug).unwrap_or(0),
+          cost: game
+            .inventory
+            .get_cost(drug)
+            .filter(|_| game.inventory.get_amount(drug).unwrap_or(0) > 0),
+        })
+        .collect(),
+      event,
+      game_over: game.game_over,
+      score: game.game_over.then(|| game.score()),
+    }
+  }
+}
+
+// MARK: serve()
+/// Plays with the client on the other end of `input` and `output`, until it hangs up.
+pub fn serve(input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+  let mut server: Option<Server> = None;
+  for line in input.lines() {
+    let line = line?;
+    if line.trim().is_empty() {
+      continue;
+    }
+    let reply = match serde_json::from_str::<Value>(&line) {
+      Err(err) => Some(Reply::Single(Response::new(
+        Value::Null,
+        Err(RpcError::new(PARSE_ERROR, err.to_string())),
+      ))),
+      Ok(Value::Array(batch)) if batch.is_empty() => Some(Reply::Single(Response::new(
+        Value::Null,
+        Err(RpcError::new(INVALID_REQUEST, "A batch can't be empty.")),
+      ))),
+      Ok(Value::Array(batch)) => {
+        let responses: Vec<Response> = batch
+          .into_iter()
+          .filter_map(|request| respond(&mut server, request))
+          .collect();
+        (!responses.is_empty()).then_some(Reply::Batch(responses))
+      }
+      Ok(request) => respond(&mut server, request).map(Reply::Single),
+    };
+    if let Some(reply) = reply {
+      serde_json::to_writer(&mut output, &reply)?;
+      writeln!(output)?;
+      output.flush()?;
+    }
+  }
+  Ok(())
+}
+
+// MARK: respond()
+/// Carries out one request, answering it unless it was a notification.
+fn respond(server: &mut Option<Server>, request: Value) -> Option<Response> {
+  let request = match serde_json::from_value::<Request>(request) {
+    Ok(request) if request.jsonrpc == "2.0" => request,
+    Ok(request) => {
+      let err = RpcError::new(INVALID_REQUEST, "Only JSON-RPC 2.0 is spoken here.");
+      return Some(Response::new(request.id.unwrap_or_default(), Err(err)));
+    }
+    Err(err) => {
+      let err = RpcError::new(INVALID_REQUEST, err.to_string());
+      return Some(Response::new(Value::Null, Err(err)));
+    }
+  };
+  let outcome = call(server, &request.method, request.params);
+  request.id.map(|id| Response::new(id, outcome))
+}
+
+// MARK: call()
+fn call(server: &mut Option<Server>, method: &str, params: Value) -> Result<State, RpcError> {
+  let invalid_params = |err: String| RpcError::new(INVALID_PARAMS, err);
+  if method == "new_game" {
+    let handshake = match params {
+      Value::Null => Handshake::default(),
+      params => serde_json::from_value::<Handshake>(params)
+        .map_err(|err| invalid_params(err.to_string()))?,
+    };
+    handshake.validate().map_err(invalid_params)?;
+    return Ok(server.insert(Server::new(handshake)).state());
+  }
+  if !COMMANDS.contains(&method) {
+    return Err(RpcError::new(
+      METHOD_NOT_FOUND,
+      format!("There's no method `{}`.", method),
+    ));
+  }
+  let Some(server) = server.as_mut() else {
+    return Err(RpcError::new(
+      NO_GAME,
+      "Start a game with `new_game` first.",
+    ));
+  };
+  let mut tagged = Map::new();
+  tagged.insert("method".to_string(), Value::from(method));
+  // empty params are fine for methods that take none
+  let empty = match &params {
+    Value::Null => true,
+    Value::Object(map) => map.is_empty(),
+    Value::Array(list) => list.is_empty(),
+    _ => false,
+  };
+  if !empty {
+    tagged.insert("params".to_string(), params);
+  }
+  let command = serde_json::from_value::<Command>(Value::Object(tagged))
+    .map_err(|err| invalid_params(err.to_string()))?;
+  server
+    .handle(command)
+    .map_err(|message| RpcError::new(REFUSED, message))?;
+  Ok(server.state())
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::{Value, json};
+
+  use super::*;
+
+  /// Sends `lines` to the server and returns its replies.
+  fn talk(lines: &[Value]) -> Vec<Value> {
+    let input: Vec<String> = lines.iter().map(Value::to_string).collect();
+    let mut output = Vec::new();
+    serve(input.join("\n").as_bytes(), &mut output).unwrap();
+    String::from_utf8(output)
+      .unwrap()
+      .lines()
+      .map(|line| serde_json::from_str(line).unwrap())
+      .collect()
+  }
+
+  fn call(id: u32, method: &str, params: Value) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params})
+  }
+
+  #[test]
+  fn plays_a_game_over_json_rpc() {
+    let replies = talk(&[
+      call(1, "new_game", json!({"seed": 42, "length": {"Custom": 2}})),
+      call(2, "buy", json!({"drug": "Weed", "amount": 1})),
+      call(3, "wait", json!({})),
+      json!({"jsonrpc": "2.0", "id": 4, "method": "wait"}),
+      call(5, "state", json!([])),
+    ]);
+    assert_eq!(replies.len(), 5);
+    for (id, reply) in (1..).zip(&replies) {
+      assert_eq!(
+        (&reply["jsonrpc"], &reply["id"]),
+        (&json!("2.0"), &json!(id))
+      );
+    }
+    let start = &replies[0]["result"];
+    assert_eq!(
+      (&start["seed"], &start["days_left"]),
+      (&json!(42), &json!(2))
+    );
+    let weed = &replies[1]["result"]["market"][0];
+    assert_eq!((&weed["drug"], &weed["held"]), (&json!("Weed"), &json!(1)));
+    assert_eq!(replies[3]["result"]["game_over"], true);
+    assert!(replies[3]["result"]["score"].is_i64());
+    assert!(replies[4]["result"].is_object());
+  }
+
+  #[test]
+  fn rejects_bad_new_games() {
+    let ranges = |first: [u64; 2]| json!([first, [1, 2], [1, 2], [1, 2], [1, 2], [1, 2], [1, 2]]);
+    let replies = talk(&[
+      call(1, "new_game", json!({"length": {"Custom": 0}})),
+      call(2, "new_game", json!({"ruleset": {"interest_rate": 1.5}})),
+      call(
+        3,
+        "new_game",
+        json!({"ruleset": {"price_ranges": ranges([0, 10])}}),
+      ),
+      call(
+        4,
+        "new_game",
+        json!({"ruleset": {"price_ranges": ranges([10, 5])}}),
+      ),
+      call(
+        5,
+        "new_game",
+        json!({"ruleset": {"price_ranges": ranges([4000000000, 4294967295])}}),
+      ),
+      call(
+        6,
+        "new_game",
+        json!({"ruleset": {"starting_cash": u64::MAX}}),
+      ),
+      call(
+        7,
+        "new_game",
+        json!({"ruleset": {"starting_debt": 1_000_001}}),
+      ),
+      call(8, "new_game", json!({"seed": "soon"})),
+      call(9, "new_game", json!({"seed": 7})),
+    ]);
+    for reply in &replies[..8] {
+      assert_eq!(reply["error"]["code"], INVALID_PARAMS, "{}", reply);
+    }
+    assert_eq!(replies[8]["result"]["seed"], 7);
+  }
+
+  #[test]
+  fn answers_bad_calls_with_standard_errors() {
+    let replies = talk(&[
+      call(1, "wait", Value::Null),
+      json!("{"),
+      call(2, "new_game", json!({"seed": 7})),
+      call(3, "fly", Value::Null),
+      call(4, "buy", json!({"drug": "Weed"})),
+      call(5, "sell", json!({"drug": "Weed", "amount": 1})),
+      json!({"jsonrpc": "1.0", "id": 6, "method": "state"}),
+      json!({"jsonrpc": "2.0", "method": "wait"}),
+      json!([call(7, "state", Value::Null), {"jsonrpc": "2.0", "method": "ack"}]),
+      json!([]),
+    ]);
+    let code = |i: usize| replies[i]["error"]["code"].as_i64();
+    assert_eq!(code(0), Some(NO_GAME.into()));
+    assert_eq!(
+      code(1),
+      Some(INVALID_REQUEST.into()),
+      "a string isn't a request"
+    );
+    assert_eq!(code(3), Some(METHOD_NOT_FOUND.into()));
+    assert_eq!(code(4), Some(INVALID_PARAMS.into()));
+    assert_eq!(code(5), Some(REFUSED.into()));
+    assert_eq!(code(6), Some(INVALID_REQUEST.into()));
+    // the notification got no reply, but it did move the game on a day
+    let batch = replies[7].as_array().unwrap();
+    assert_eq!(batch.len(), 1);
+    assert_eq!(batch[0]["result"]["day"], 1);
+    assert_eq!(code(8), Some(INVALID_REQUEST.into()));
+    assert_eq!(replies.len(), 9);
+  }
+
+  #[test]
+  fn reports_unparsable_lines() {
+    let mut output = Vec::new();
+    serve("{\"jsonrpc\": ".as_bytes(), &mut output).unwrap();
+    let reply: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(reply["error"]["code"], PARSE_ERROR);
+    assert_eq!(reply["id"], Value::Null);
+  }
+}
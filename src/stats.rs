@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bus::{GameEvent, Subscriber};
+use crate::drugs::Drug;
+use crate::events::{Event, EventType};
+use crate::locations::Location;
+use crate::money::Money;
+use crate::storage;
+
+const STATS_FILE: &str = "stats.json";
+
+// MARK: - Stats struct
+/// Running totals, fed by the engine's published events.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+  pub games_played: u32,
+  pub total_profit: i64,
+  pub biggest_sale: Money,
+  pub events_seen: u32,
+  pub times_mugged: u32,
+  pub lost_to_muggings: Money,
+  pub drug_profits: HashMap<Drug, i64>,
+  pub location_visits: HashMap<Location, u32>,
+}
+
+impl Stats {
+  // MARK: load()
+  pub fn load() -> Self {
+    storage::load(STATS_FILE)
+  }
+
+  // MARK: save()
+  pub fn save(&self) -> std::io::Result<()> {
+    storage::save(STATS_FILE, self)
+  }
+
+  // MARK: record_sale()
+  fn record_sale(&mut self, drug: Drug, amount: u32, price: Money, cost: Money) {
+    self.biggest_sale = self.biggest_sale.max(price.times(amount));
+    let profit = (price.signed() - cost.signed()).saturating_mul(amount as i64);
+    let total = self.drug_profits.entry(drug).or_insert(0);
+    *total = total.saturating_add(profit);
+  }
+
+  // MARK: record_travel()
+  fn record_travel(&mut self, location: Location) {
+    *self.location_visits.entry(location).or_insert(0) += 1;
+  }
+
+  // MARK: record_event()
+  /// Counts an event; `lost` is the value of the cash and product it cost the player.
+  fn record_event(&mut self, event: &Event, lost: Money) {
+    self.events_seen += 1;
+    if event.e_type == EventType::Mugging {
+      self.times_mugged += 1;
+      self.lost_to_muggings += lost;
+    }
+  }
+
+  // MARK: record_game()
+  fn record_game(&mut self, profit: i64) {
+    self.games_played += 1;
+    self.total_profit = self.total_profit.saturating_add(profit);
+  }
+
+  // MARK: best_drug()
+  pub fn best_drug(&self) -> Option<(Drug, i64)> {
+    self
+      .drug_profits
+      .iter()
+      .max_by_key(|(drug, profit)| (**profit, drug.as_index()))
+      .map(|(drug, profit)| (*drug, *profit))
+  }
+
+  // MARK: favourite_location()
+  pub fn favourite_location(&self) -> Option<(Location, u32)> {
+    self
+      .location_visits
+      .iter()
+      .max_by_key(|(location, visits)| (**visits, **location as usize))
+      .map(|(location, visits)| (*location, *visits))
+  }
+}
+
+// MARK: Subscriber impl
+impl Subscriber for Stats {
+  fn notify(&mut self, event: &GameEvent) {
+    match event {
+      GameEvent::Sold {
+        drug,
+        amount,
+        price,
+        cost,
+      } => self.record_sale(*drug, *amount, *price, *cost),
+      GameEvent::Traveled { to, .. } => self.record_travel(*to),
+      GameEvent::EventTriggered { event, lost } => self.record_event(event, *lost),
+      GameEvent::GameEnded { profit, .. } => self.record_game(*profit),
+      _ => {}
+    }
+  }
+}
@@ -0,0 +1,54 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+const APP_ID: &str = "Dank Bytes";
+
+// MARK: data_path() [helper]
+fn data_path(file: &str) -> Option<PathBuf> {
+  eframe::storage_dir(APP_ID).map(|dir| dir.join(file))
+}
+
+// MARK: load()
+/// Reads a JSON file from the app's data directory, or the default if it's missing or unreadable.
+pub fn load<T: DeserializeOwned + Default>(file: &str) -> T {
+  data_path(file)
+    .and_then(|path| fs::read_to_string(path).ok())
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+// MARK: save()
+pub fn save<T: Serialize>(file: &str, value: &T) -> io::Result<()> {
+  let path =
+    data_path(file).ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+  if let Some(dir) = path.parent() {
+    fs::create_dir_all(dir)?;
+  }
+  fs::write(path, serde_json::to_string_pretty(value)?)
+}
+
+// MARK: today()
+/// Today's UTC date as `YYYY-MM-DD`.
+pub fn today() -> String {
+  let days = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|elapsed| elapsed.as_secs() / 86_400)
+    .unwrap_or(0) as i64;
+
+  // civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+  let z = days + 719_468;
+  let era = z.div_euclid(146_097);
+  let doe = z.rem_euclid(146_097);
+  let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = doy - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = yoe + era * 400 + i64::from(month <= 2);
+  format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+
+use crate::drugs::Drug;
+use crate::inventory::InventoryError;
+use crate::money::Money;
+
+// MARK: TradeReceipt enum
+/// What a successful trade or repayment did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeReceipt {
+  Bought {
+    drug: Drug,
+    amount: u32,
+    price: Money,
+    total: Money,
+  },
+  Sold {
+    drug: Drug,
+    amount: u32,
+    price: Money,
+    total: Money,
+  },
+  Repaid {
+    amount: Money,
+    remaining: Money,
+  },
+}
+
+// MARK: TradeReceipt display
+impl fmt::Display for TradeReceipt {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      TradeReceipt::Bought {
+        drug,
+        amount,
+        total,
+        ..
+      } => write!(f, "Bought {} {} for {}", amount, drug, total),
+      TradeReceipt::Sold {
+        drug,
+        amount,
+        total,
+        ..
+      } => write!(f, "Sold {} {} for {}", amount, drug, total),
+      TradeReceipt::Repaid { amount, .. } => write!(f, "Repaid {} of debt", amount),
+    }
+  }
+}
+
+// MARK: - TradeError enum
+/// Why a trade or repayment didn't go through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeError {
+  InvalidAmount,
+  InsufficientCash,
+  InsufficientStock,
+  InsufficientSpace,
+  NothingOwed,
+  NothingToSell,
+  NoProfit,
+  StillNeeded,
+  GameOver,
+}
+
+impl From<InventoryError> for TradeError {
+  fn from(_: InventoryError) -> Self {
+    TradeError::InsufficientStock
+  }
+}
+
+// MARK: TradeError display
+impl fmt::Display for TradeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      TradeError::InvalidAmount => write!(f, "Pick an amount first."),
+      TradeError::InsufficientCash => write!(f, "You can't afford that."),
+      TradeError::InsufficientStock => write!(f, "You don't have that many to sell."),
+      TradeError::InsufficientSpace => write!(f, "You don't have room to carry that."),
+      TradeError::NothingOwed => write!(f, "You don't owe anything."),
+      TradeError::NothingToSell => write!(f, "You don't have anything to sell here."),
+      TradeError::NoProfit => write!(f, "Nothing you're holding sells at a profit here."),
+      TradeError::StillNeeded => write!(f, "You need it to carry what you're holding."),
+      TradeError::GameOver => write!(f, "The game is over."),
+    }
+  }
+}
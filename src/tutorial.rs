@@ -0,0 +1,139 @@
+use crate::bus::{GameEvent, Subscriber};
+
+// MARK: tutorial constants
+/// The tutorial always plays on this seed, so its market is the same every time.
+pub const TUTORIAL_SEED: u64 = 1337;
+
+// MARK: Highlight enum
+/// A part of the screen the tutorial can point at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+  Money,
+  Trading,
+  Travel,
+  Events,
+  Repay,
+}
+
+// MARK: - Step enum
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+  #[default]
+  Welcome,
+  Buy,
+  Travel,
+  Events,
+  Sell,
+  Repay,
+  Done,
+}
+
+impl Step {
+  // MARK: title()
+  pub fn title(&self) -> &'static str {
+    match self {
+      Step::Welcome => "Welcome to Dank Bytes",
+      Step::Buy => "Buying",
+      Step::Travel => "Traveling",
+      Step::Events => "Events",
+      Step::Sell => "Selling",
+      Step::Repay => "Paying off your debt",
+      Step::Done => "You're on your own",
+    }
+  }
+
+  // MARK: text()
+  pub fn text(&self) -> &'static str {
+    match self {
+      Step::Welcome => {
+        "You start with a little cash and a debt that grows every day. \
+         Make as much money as you can before the days run out."
+      }
+      Step::Buy => {
+        "Prices are listed in the trading table. \
+         Pick an amount in the Buy column for something cheap and buy it."
+      }
+      Step::Travel => {
+        "Each neighbourhood has its own prices. \
+         Travel somewhere else; every trip takes a day."
+      }
+      Step::Events => {
+        "Things happen on the way: busts, shipments, muggings and the police. \
+         Whatever happened shows up in the bar at the bottom of the screen."
+      }
+      Step::Sell => {
+        "Busts push prices up and shipments bring them down. \
+         Sell what you bought, ideally for more than you paid."
+      }
+      Step::Repay => {
+        "Interest is charged on your debt every day. \
+         Drag the slider and repay some of it."
+      }
+      Step::Done => "That's everything. Buy low, sell high, and watch out for the cops.",
+    }
+  }
+
+  // MARK: target()
+  pub fn target(&self) -> Option<Highlight> {
+    match self {
+      Step::Welcome => Some(Highlight::Money),
+      Step::Buy | Step::Sell => Some(Highlight::Trading),
+      Step::Travel => Some(Highlight::Travel),
+      Step::Events => Some(Highlight::Events),
+      Step::Repay => Some(Highlight::Repay),
+      Step::Done => None,
+    }
+  }
+
+  // MARK: is_manual()
+  /// Whether the player moves on by clicking through rather than by playing.
+  pub fn is_manual(&self) -> bool {
+    matches!(self, Step::Welcome | Step::Events | Step::Done)
+  }
+
+  fn next(self) -> Step {
+    match self {
+      Step::Welcome => Step::Buy,
+      Step::Buy => Step::Travel,
+      Step::Travel => Step::Events,
+      Step::Events => Step::Sell,
+      Step::Sell => Step::Repay,
+      Step::Repay | Step::Done => Step::Done,
+    }
+  }
+}
+
+// MARK: - Tutorial struct
+/// A guided first game, moved along by the player's own actions.
+#[derive(Debug, Default, Clone)]
+pub struct Tutorial {
+  step: Step,
+}
+
+impl Tutorial {
+  // MARK: step()
+  pub fn step(&self) -> Step {
+    self.step
+  }
+
+  // MARK: advance()
+  pub fn advance(&mut self) {
+    self.step = self.step.next();
+  }
+}
+
+// MARK: Subscriber impl
+impl Subscriber for Tutorial {
+  fn notify(&mut self, event: &GameEvent) {
+    let completed = matches!(
+      (self.step, event),
+      (Step::Buy, GameEvent::Bought { .. })
+        | (Step::Travel, GameEvent::Traveled { .. })
+        | (Step::Sell, GameEvent::Sold { .. })
+        | (Step::Repay, GameEvent::DebtRepaid { .. })
+    );
+    if completed {
+      self.advance();
+    }
+  }
+}
@@ -4,8 +4,42 @@ use egui_extras::Column;
 use hello_egui::material_icons::icons;
 use thousands::Separable;
 
-use crate::game::GameLength;
-use crate::{drugs::get_drug_list, events, game::Game, locations::Location};
+use crate::achievements::get_achievement_list;
+use crate::advisor::Risk;
+use crate::autopilot::{MAX_AUTOPILOT_DAYS, MAX_AUTOPILOT_SPEED, MIN_AUTOPILOT_SPEED};
+use crate::basket::Order;
+use crate::bus::GameEvent;
+use crate::equipment::get_equipment_list;
+use crate::game::{DEFAULT_CUSTOM_DAYS, GameLength, MAX_CUSTOM_DAYS};
+use crate::highscores::table_name;
+use crate::money::Money;
+use crate::networth::Valuation;
+use crate::replay::ResultCode;
+use crate::ruleset::{Difficulty, MAX_PRICE, MAX_STARTING_MONEY, Ruleset, get_difficulty_list};
+use crate::rumours::INFORMANT_FEE;
+use crate::scenarios::{Outcome, ScenarioRun, get_scenario_list};
+use crate::trade::{TradeError, TradeReceipt};
+use crate::tutorial::{Highlight, Step};
+use crate::{drugs::get_drug_list, events, game::Game, locations::get_location_list};
+
+const TOAST_SECONDS: f64 = 4.0;
+
+// MARK: - Toast struct
+/// A short message shown in the corner for a few seconds.
+#[derive(Debug, Clone)]
+pub struct Toast {
+  pub text: String,
+  expires_at: Option<f64>,
+}
+
+impl Toast {
+  pub fn new(text: impl Into<String>) -> Self {
+    Self {
+      text: text.into(),
+      expires_at: None,
+    }
+  }
+}
 
 // MARK: - render_window()
 pub fn render_window(game: &mut Game, ctx: &egui::Context) {
@@ -13,11 +47,39 @@ pub fn render_window(game: &mut Game, ctx: &egui::Context) {
   if ctx.input(|i| i.key_pressed(egui::Key::F12)) {
     game.toggle_dev_mode();
   }
+  if !game.init && ctx.input(|i| i.modifiers.command && i.key_pressed(egui::Key::Z)) {
+    undo_trade(game);
+  }
+  if !game.init && !game.game_over && !ctx.wants_keyboard_input() {
+    handle_trade_hotkeys(game, ctx);
+  }
+  if !game.init && game.autopilot.is_running() {
+    game.run_autopilot(ctx.input(|i| i.time));
+    ctx.request_repaint();
+  }
 
   if game.dev_mode {
     render_dev_window(game, ctx);
   }
 
+  for event in game.bus.drain() {
+    handle_game_event(game, &event);
+  }
+
+  let unlocked = game.achievements.take_unlocked();
+  if !unlocked.is_empty() {
+    for achievement in unlocked {
+      game.toasts.push(Toast::new(format!(
+        "🏆 Achievement unlocked: {}",
+        achievement.name()
+      )));
+    }
+  }
+  if game.achievements.take_changed() {
+    game.achievements.save().ok();
+  }
+  render_toasts(game, ctx);
+
   let mut init = game.init;
   egui::CentralPanel::default().show(ctx, |ui| {
     if init {
@@ -30,24 +92,61 @@ pub fn render_window(game: &mut Game, ctx: &egui::Context) {
         .open(&mut init)
         .show(ctx, |ui| {
           ui.vertical_centered(|ui| {
-            ui.label("Game Length:");
+            ui.label("Scenario:");
             ui.add_space(5.0);
-            ui.vertical_centered_justified(|ui| {
-              ui.radio_value(&mut game.game_length, GameLength::Short, "One Month");
-              ui.radio_value(&mut game.game_length, GameLength::Medium, "Six Months");
-              ui.radio_value(&mut game.game_length, GameLength::Long, "One Year");
+            ui.add_enabled_ui(!game.daily, |ui| {
+              render_scenario_picker(game, ui);
             });
             ui.add_space(5.0);
+            if game.scenario.is_none() {
+              ui.label("Game Length:");
+              ui.add_space(5.0);
+              render_length_picker(game, ui);
+              ui.add_space(5.0);
+              ui.checkbox(&mut game.daily, "Daily Challenge")
+                .on_hover_text("Today's seed and the normal rules, the same for everyone.");
+              if !game.daily {
+                ui.add_space(5.0);
+                ui.label("Difficulty:");
+                ui.add_space(5.0);
+                render_ruleset_picker(game, ui);
+              }
+              ui.add_space(5.0);
+            }
             if ui.button("Start").clicked() {
-              game.days_left = match game.game_length {
-                GameLength::Short => 30,
-                GameLength::Medium => 180,
-                GameLength::Long => 360,
-              };
-              game.init = false;
+              if game.daily {
+                game.start_daily();
+              } else {
+                game.start();
+              }
+            }
+            if ui
+              .button("Tutorial")
+              .on_hover_text("A guided first game.")
+              .clicked()
+            {
+              game.start_tutorial();
+            }
+            if ui.button("High Scores").clicked() {
+              game.show_high_scores = !game.show_high_scores;
+            }
+            if ui.button("Stats").clicked() {
+              game.show_stats = !game.show_stats;
+            }
+            if ui.button("Achievements").clicked() {
+              game.show_achievements = !game.show_achievements;
             }
           });
         });
+      if game.show_high_scores {
+        render_high_scores_window(game, ctx);
+      }
+      if game.show_stats {
+        render_stats_window(game, ctx);
+      }
+      if game.show_achievements {
+        render_achievements_window(game, ctx);
+      }
       return;
     }
     // MARK: main game window
@@ -56,16 +155,25 @@ pub fn render_window(game: &mut Game, ctx: &egui::Context) {
         egui::Layout::top_down(egui::Align::LEFT).with_main_wrap(true),
         |ui| {
           // MARK: bottom bar
-          egui::TopBottomPanel::bottom("bottom_panel")
+          let bottom_bar = egui::TopBottomPanel::bottom("bottom_panel")
             .resizable(false)
             .exact_height(40.0)
             .show(ctx, |ui| {
-              if game.event.is_some() {
+              if let Some(event) = game.event.clone() {
                 ui.horizontal(|ui| {
-                  ui.label(game.event.as_ref().unwrap().e_msg.clone());
+                  ui.label(event.e_msg);
+                  if let Some((_, price)) = event.e_offer
+                    && ui
+                      .add_enabled(game.cash >= price, Button::new("Buy"))
+                      .on_disabled_hover_text("You can't afford it.")
+                      .clicked()
+                  {
+                    game.accept_offer();
+                  }
                 });
               }
             });
+          highlight(game, ui, Highlight::Events, bottom_bar.response.rect);
           // MARK: main section
           ui.with_layout(
             egui::Layout::left_to_right(egui::Align::Center).with_main_wrap(true),
@@ -74,19 +182,38 @@ pub fn render_window(game: &mut Game, ctx: &egui::Context) {
               right_panel(game, ctx);
             },
           );
+          if game.show_equipment {
+            render_equipment_window(game, ctx);
+          }
+          if game.show_rumours {
+            render_rumours_window(game, ctx);
+          }
+          if game.show_autopilot && game.ruleset.advisor_enabled {
+            render_autopilot_window(game, ctx);
+          }
+          if game.tutorial.is_some() {
+            render_tutorial_window(game, ctx);
+          }
         },
       );
     });
     // MARK: game over section
-    if game.days_left == 0 || (game.cash == 0 && !game.inventory.has_items()) {
-      game.game_over = true;
+    if game.game_over {
       let mut game_over = game.game_over;
-      let game_over_message = if game.days_left == 0 {
-        "Game Over! You have run out of time."
-      } else if game.cash == 0 && !game.inventory.has_items() {
-        "Game Over! You are out of cash and have no items to sell."
+      let game_over_message = if let Some(run) = &game.scenario
+        && run.outcome == Some(Outcome::Won)
+      {
+        format!("Scenario complete! {}.", run.scenario.goal)
+      } else if let Some(run) = &game.scenario {
+        format!("Scenario failed. {}.", run.scenario.goal)
+      } else if game.retired {
+        "Game Over! You have retired from the game.".to_string()
+      } else if game.days_left == Some(0) {
+        "Game Over! You have run out of time.".to_string()
+      } else if game.is_bankrupt() {
+        "Game Over! You are out of cash and have nothing left to sell.".to_string()
       } else {
-        "Game Over!"
+        "Game Over!".to_string()
       };
 
       egui::Window::new("Game Over")
@@ -98,32 +225,34 @@ pub fn render_window(game: &mut Game, ctx: &egui::Context) {
           ui.vertical_centered(|ui| {
             ui.label(game_over_message);
             ui.add_space(5.0);
-            ui.label(format!("Final Cash: ${}", game.cash.separate_with_commas()));
-            ui.label(format!("Final Debt: ${}", game.debt.separate_with_commas()));
-
-            let (score, color) = if game.cash < game.debt {
-              (
-                format!(
-                  "-{}",
-                  (game.debt.saturating_sub(game.cash)).separate_with_commas()
-                ),
-                Color32::LIGHT_RED,
-              )
+            let worth = game.net_worth(Valuation::Current);
+            ui.label(format!("Final Cash: {}", worth.cash));
+            ui.label(format!("Product Value: {}", worth.inventory));
+            ui.label(format!("Equipment Value: {}", worth.equipment));
+            ui.label(format!("Final Debt: {}", worth.debt));
+
+            if game.game_length == GameLength::Endless {
+              ui.label(format!("Days Played: {}", game.day));
+            }
+
+            let score = game.score();
+            let color = if score < 0 {
+              Color32::LIGHT_RED
             } else {
-              (
-                game.cash.saturating_sub(game.debt).separate_with_commas(),
-                Color32::LIGHT_GREEN,
-              )
+              Color32::LIGHT_GREEN
             };
 
             let mut job = LayoutJob::default();
             job.append(
-              "Final Score: ",
+              match game.game_length {
+                GameLength::Endless => "Profit per Day: ",
+                _ => "Final Score: ",
+              },
               0.0,
               TextFormat::simple(FontId::default(), Color32::GRAY),
             );
             job.append(
-              score.as_str(),
+              score.separate_with_commas().as_str(),
               0.0,
               TextFormat::simple(FontId::default(), color),
             );
@@ -131,6 +260,44 @@ pub fn render_window(game: &mut Game, ctx: &egui::Context) {
 
             ui.add_space(5.0);
 
+            // MARK: daily result code
+            if game.daily {
+              let mut code = ResultCode::of(game).to_string();
+              ui.label("Share your daily challenge result code:");
+              ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut code).desired_width(160.0));
+                if ui.button("Copy").clicked() {
+                  ui.ctx().copy_text(code.clone());
+                }
+              });
+              ui.add_space(5.0);
+            }
+
+            // MARK: high score entry
+            if game.score_saved {
+              match game.score_rank {
+                Some(rank) => ui.label(format!("You placed #{} on the high score table!", rank)),
+                None => ui.label("You didn't make the high score table."),
+              };
+            } else if game.is_ranked()
+              && game
+                .high_scores
+                .qualifies(game.game_length, &game.ruleset, score)
+            {
+              ui.label("New high score! Enter your name:");
+              ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut game.player_name).desired_width(120.0));
+                if ui
+                  .add_enabled(!game.player_name.trim().is_empty(), Button::new("Save"))
+                  .clicked()
+                {
+                  game.record_high_score();
+                }
+              });
+            }
+
+            ui.add_space(5.0);
+
             if ui.button("OK").clicked() {
               game.reset();
             }
@@ -140,6 +307,480 @@ pub fn render_window(game: &mut Game, ctx: &egui::Context) {
   });
 }
 
+// MARK: render_tutorial_window()
+fn render_tutorial_window(game: &mut Game, ctx: &egui::Context) {
+  let Some(step) = game.tutorial.as_ref().map(|tutorial| tutorial.step()) else {
+    return;
+  };
+  egui::Window::new("Tutorial")
+    .anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(10.0, -50.0))
+    .max_width(240.0)
+    .collapsible(false)
+    .resizable(false)
+    .show(ctx, |ui| {
+      ui.strong(step.title());
+      ui.label(step.text());
+      ui.add_space(5.0);
+      ui.horizontal(|ui| {
+        if step == Step::Done {
+          if ui.button("Finish").clicked() {
+            game.tutorial = None;
+          }
+          return;
+        }
+        if step.is_manual()
+          && ui.button("Next").clicked()
+          && let Some(tutorial) = game.tutorial.as_mut()
+        {
+          tutorial.advance();
+        }
+        if ui.button("Skip Tutorial").clicked() {
+          game.tutorial = None;
+        }
+      });
+    });
+}
+
+// MARK: highlight()
+/// Outlines `rect` while the tutorial is pointing the player at `target`.
+fn highlight(game: &Game, ui: &egui::Ui, target: Highlight, rect: egui::Rect) {
+  let Some(tutorial) = &game.tutorial else {
+    return;
+  };
+  if tutorial.step().target() != Some(target) {
+    return;
+  }
+  ui.ctx()
+    .layer_painter(egui::LayerId::new(
+      egui::Order::Foreground,
+      egui::Id::new("tutorial_highlight"),
+    ))
+    .rect_stroke(
+      rect.expand(2.0),
+      4.0,
+      egui::Stroke::new(2.0, Color32::GOLD),
+      egui::StrokeKind::Outside,
+    );
+}
+
+// MARK: render_scenario_picker()
+fn render_scenario_picker(game: &mut Game, ui: &mut egui::Ui) {
+  let selected = game
+    .scenario
+    .as_ref()
+    .map_or("Free Play", |run| run.scenario.name.as_str())
+    .to_string();
+  egui::ComboBox::from_id_salt("scenario_picker")
+    .selected_text(selected)
+    .show_ui(ui, |ui| {
+      if ui
+        .selectable_label(game.scenario.is_none(), "Free Play")
+        .clicked()
+      {
+        game.scenario = None;
+      }
+      for scenario in get_scenario_list() {
+        let is_selected = game
+          .scenario
+          .as_ref()
+          .is_some_and(|run| run.scenario == *scenario);
+        if ui.selectable_label(is_selected, &scenario.name).clicked() {
+          game.scenario = Some(ScenarioRun::new(scenario.clone()));
+        }
+      }
+    });
+  if let Some(run) = &game.scenario {
+    ui.add_space(5.0);
+    ui.label(&run.scenario.description);
+    ui.label(format!("Goal: {}", run.scenario.goal));
+  }
+}
+
+// MARK: render_length_picker()
+fn render_length_picker(game: &mut Game, ui: &mut egui::Ui) {
+  let custom = match game.game_length {
+    GameLength::Custom(days) => GameLength::Custom(days),
+    _ => GameLength::Custom(DEFAULT_CUSTOM_DAYS),
+  };
+  ui.vertical_centered_justified(|ui| {
+    for length in [
+      GameLength::Short,
+      GameLength::Medium,
+      GameLength::Long,
+      custom,
+      GameLength::Endless,
+    ] {
+      let label = match length {
+        GameLength::Custom(_) => "Custom".to_string(),
+        _ => length.to_string(),
+      };
+      ui.radio_value(&mut game.game_length, length, label);
+    }
+  });
+  if let GameLength::Custom(days) = &mut game.game_length {
+    ui.add(
+      egui::DragValue::new(days)
+        .suffix(" days")
+        .range(1..=MAX_CUSTOM_DAYS),
+    );
+  }
+}
+
+// MARK: render_ruleset_picker()
+fn render_ruleset_picker(game: &mut Game, ui: &mut egui::Ui) {
+  let mut difficulty = game.ruleset.difficulty;
+  ui.horizontal(|ui| {
+    for option in get_difficulty_list() {
+      ui.selectable_value(&mut difficulty, option, option.to_string());
+    }
+  });
+  if difficulty != game.ruleset.difficulty {
+    game.ruleset = match difficulty {
+      Difficulty::Custom => Ruleset {
+        difficulty,
+        ..game.ruleset.clone()
+      },
+      preset => Ruleset::preset(preset),
+    };
+  }
+  ui.checkbox(&mut game.ruleset.advisor_enabled, "Trade advisor")
+    .on_hover_text("Suggests what to buy here and where to sell it, from prices you've seen. The autopilot needs it.");
+  if difficulty != Difficulty::Custom {
+    return;
+  }
+
+  let ruleset = &mut game.ruleset;
+  egui::Grid::new("custom_ruleset").show(ui, |ui| {
+    ui.label("Starting Cash");
+    ui.add(
+      egui::DragValue::new(&mut ruleset.starting_cash)
+        .prefix("$")
+        .range(Money::ZERO..=MAX_STARTING_MONEY),
+    );
+    ui.end_row();
+    ui.label("Starting Debt");
+    ui.add(
+      egui::DragValue::new(&mut ruleset.starting_debt)
+        .prefix("$")
+        .range(Money::ZERO..=MAX_STARTING_MONEY),
+    );
+    ui.end_row();
+    for (label, value) in [
+      ("Interest Rate", &mut ruleset.interest_rate),
+      ("Event Chance", &mut ruleset.event_chance),
+      ("Mugging Cash Ratio", &mut ruleset.mugging_cash_ratio),
+    ] {
+      ui.label(label);
+      ui.add(
+        egui::DragValue::new(value)
+          .range(0.0..=1.0)
+          .speed(0.005)
+          .fixed_decimals(3),
+      );
+      ui.end_row();
+    }
+  });
+  ui.collapsing("Price Ranges", |ui| {
+    egui::Grid::new("custom_prices").show(ui, |ui| {
+      for drug in get_drug_list() {
+        let (min, max) = &mut ruleset.price_ranges[drug.as_index()];
+        ui.label(drug.to_string());
+        ui.add(egui::DragValue::new(min).prefix("$").range(1..=MAX_PRICE));
+        ui.add(egui::DragValue::new(max).prefix("$").range(1..=MAX_PRICE));
+        *max = (*max).max(*min);
+        ui.end_row();
+      }
+    });
+  });
+}
+
+// MARK: render_high_scores_window()
+fn render_high_scores_window(game: &mut Game, ctx: &egui::Context) {
+  let mut open = game.show_high_scores;
+  egui::Window::new("High Scores")
+    .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+    .collapsible(false)
+    .resizable(false)
+    .open(&mut open)
+    .show(ctx, |ui| {
+      ui.horizontal_wrapped(|ui| {
+        let mut tabs: Vec<String> = [
+          GameLength::Short,
+          GameLength::Medium,
+          GameLength::Long,
+          GameLength::Endless,
+        ]
+        .iter()
+        .map(GameLength::to_string)
+        .collect();
+        for length in game.high_scores.lengths() {
+          if !tabs.iter().any(|tab| tab == length) {
+            tabs.push(length.to_string());
+          }
+        }
+        for tab in tabs {
+          ui.selectable_value(&mut game.high_score_tab, tab.clone(), tab);
+        }
+      });
+      ui.horizontal(|ui| {
+        for difficulty in get_difficulty_list() {
+          ui.selectable_value(
+            &mut game.high_score_difficulty,
+            difficulty,
+            difficulty.to_string(),
+          );
+        }
+        ui.separator();
+        ui.checkbox(&mut game.high_score_advisor, "Trade advisor")
+          .on_hover_text("Runs played with the advisor's help are ranked apart.");
+      });
+      ui.separator();
+      let table = table_name(
+        &game.high_score_tab,
+        game.high_score_difficulty,
+        game.high_score_advisor,
+      );
+      let scores = game.high_scores.table(&table);
+      if scores.is_empty() {
+        ui.label("No scores yet.");
+        return;
+      }
+      egui_extras::TableBuilder::new(ui)
+        .striped(true)
+        .columns(Column::auto(), 6)
+        .header(12.0, |mut header| {
+          for title in ["#", "Name", "Score", "Difficulty", "Date", "Seed"] {
+            header.col(|ui| {
+              ui.label(title);
+            });
+          }
+        })
+        .body(|mut body| {
+          for (rank, entry) in scores.iter().enumerate() {
+            body.row(18.0, |mut row| {
+              row.col(|ui| {
+                ui.label((rank + 1).to_string());
+              });
+              row.col(|ui| {
+                ui.label(&entry.name);
+              });
+              row.col(|ui| {
+                ui.label(format_money(entry.score))
+                  .on_hover_text(format!("Cash: {}\nDebt: {}", entry.cash, entry.debt));
+              });
+              row.col(|ui| {
+                ui.label(entry.ruleset.difficulty.to_string());
+              });
+              row.col(|ui| {
+                ui.label(&entry.date);
+              });
+              row.col(|ui| {
+                ui.label(format!("{:016x}", entry.seed));
+              });
+            });
+          }
+        });
+    });
+  game.show_high_scores = open;
+}
+
+// MARK: render_stats_window()
+fn render_stats_window(game: &mut Game, ctx: &egui::Context) {
+  let mut open = game.show_stats;
+  let career = &game.career;
+  egui::Window::new("Career Stats")
+    .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+    .collapsible(false)
+    .resizable(false)
+    .open(&mut open)
+    .show(ctx, |ui| {
+      let best_drug = career
+        .best_drug()
+        .map(|(drug, profit)| format!("{} ({})", drug, format_money(profit)))
+        .unwrap_or_else(|| "-".to_string());
+      let favourite_location = career
+        .favourite_location()
+        .map(|(location, visits)| format!("{} ({} visits)", location, visits))
+        .unwrap_or_else(|| "-".to_string());
+      let rows = [
+        ("Games Played", career.games_played.to_string()),
+        ("Total Profit", format_money(career.total_profit)),
+        ("Biggest Sale", career.biggest_sale.to_string()),
+        ("Times Mugged", career.times_mugged.to_string()),
+        ("Lost to Muggings", career.lost_to_muggings.to_string()),
+        ("Best Drug", best_drug),
+        ("Favourite Location", favourite_location),
+      ];
+      egui::Grid::new("career_stats")
+        .striped(true)
+        .show(ui, |ui| {
+          for (label, value) in rows {
+            ui.label(label);
+            ui.label(value);
+            ui.end_row();
+          }
+        });
+    });
+  game.show_stats = open;
+}
+
+// MARK: format_money()
+fn format_money(amount: i64) -> String {
+  if amount < 0 {
+    format!("-${}", amount.unsigned_abs().separate_with_commas())
+  } else {
+    format!("${}", amount.separate_with_commas())
+  }
+}
+
+// MARK: render_toasts()
+fn render_toasts(game: &mut Game, ctx: &egui::Context) {
+  let now = ctx.input(|i| i.time);
+  for toast in game.toasts.iter_mut() {
+    toast.expires_at.get_or_insert(now + TOAST_SECONDS);
+  }
+  game
+    .toasts
+    .retain(|toast| toast.expires_at.is_some_and(|t| t > now));
+  if game.toasts.is_empty() {
+    return;
+  }
+
+  egui::Area::new(egui::Id::new("toasts"))
+    .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-8.0, 8.0))
+    .interactable(false)
+    .show(ctx, |ui| {
+      for toast in &game.toasts {
+        egui::Frame::popup(ui.style()).show(ui, |ui| {
+          ui.label(&toast.text);
+        });
+      }
+    });
+  ctx.request_repaint_after_secs(0.5);
+}
+
+// MARK: report_trade()
+/// Toasts the reason a trade or repayment didn't go through.
+fn report_trade(game: &mut Game, result: Result<TradeReceipt, TradeError>) {
+  if let Err(err) = result {
+    game.toasts.push(Toast::new(format!("⚠ {}", err)));
+  }
+}
+
+// MARK: report_bulk_sale()
+/// Toasts what a bulk sale made, or why nothing was sold.
+fn report_bulk_sale(game: &mut Game, result: Result<Vec<TradeReceipt>, TradeError>) {
+  let text = match result {
+    Ok(receipts) => {
+      let total: Money = receipts
+        .iter()
+        .filter_map(|receipt| match receipt {
+          TradeReceipt::Sold { total, .. } => Some(*total),
+          _ => None,
+        })
+        .sum();
+      format!("💰 Sold {} drug(s) for {}", receipts.len(), total)
+    }
+    Err(err) => format!("⚠ {}", err),
+  };
+  game.toasts.push(Toast::new(text));
+}
+
+// MARK: handle_trade_hotkeys()
+/// 1-7 buys as much as possible of a drug, Shift+1-7 sells all of it,
+/// L sells everything and P sells whatever is in profit.
+fn handle_trade_hotkeys(game: &mut Game, ctx: &egui::Context) {
+  const DRUG_KEYS: [egui::Key; 7] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+  ];
+  let (shift, pressed) = ctx.input(|i| {
+    let pressed = DRUG_KEYS.iter().position(|key| i.key_pressed(*key));
+    (i.modifiers.shift, pressed)
+  });
+  if let Some(index) = pressed {
+    let drug = get_drug_list()[index];
+    let result = match shift {
+      true => game.sell_all(drug),
+      false => game.buy_max(drug),
+    };
+    report_trade(game, result);
+  }
+  if ctx.input(|i| i.key_pressed(egui::Key::L)) {
+    let result = game.sell_everything();
+    report_bulk_sale(game, result);
+  }
+  if ctx.input(|i| i.key_pressed(egui::Key::P)) {
+    let result = game.sell_profitable();
+    report_bulk_sale(game, result);
+  }
+}
+
+// MARK: undo_trade()
+fn undo_trade(game: &mut Game) {
+  if game.undo.last().is_none() {
+    return;
+  }
+  if let Some(receipts) = game.undo() {
+    game.toasts.push(Toast::new(format!(
+      "↩ Undid: {}",
+      describe_receipts(&receipts)
+    )));
+  }
+}
+
+// MARK: describe_receipts()
+fn describe_receipts(receipts: &[TradeReceipt]) -> String {
+  let trades: Vec<String> = receipts.iter().map(TradeReceipt::to_string).collect();
+  trades.join(", ")
+}
+
+// MARK: render_achievements_window()
+fn render_achievements_window(game: &mut Game, ctx: &egui::Context) {
+  let mut open = game.show_achievements;
+  egui::Window::new("Achievements")
+    .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+    .collapsible(false)
+    .resizable(false)
+    .open(&mut open)
+    .show(ctx, |ui| {
+      egui::Grid::new("achievements")
+        .striped(true)
+        .show(ui, |ui| {
+          for achievement in get_achievement_list() {
+            let progress = game.achievements.get(achievement);
+            ui.label(achievement.name())
+              .on_hover_text(achievement.description());
+            ui.add(
+              egui::ProgressBar::new(progress.progress as f32 / achievement.goal() as f32)
+                .desired_width(100.0)
+                .text(format!("{}/{}", progress.progress, achievement.goal())),
+            );
+            ui.label(
+              progress
+                .unlocked
+                .map(|date| format!("Unlocked {}", date))
+                .unwrap_or_default(),
+            );
+            ui.end_row();
+          }
+        });
+    });
+  game.show_achievements = open;
+}
+
+// MARK: handle_game_event()
+fn handle_game_event(game: &mut Game, event: &GameEvent) {
+  if let GameEvent::GameEnded { .. } = event {
+    game.career.save().ok();
+  }
+}
+
 // MARK: - DEV render_dev_window()
 fn render_dev_window(game: &mut Game, ctx: &egui::Context) {
   use crate::drugs::get_rand_prices;
@@ -169,14 +810,14 @@ fn render_dev_window(game: &mut Game, ctx: &egui::Context) {
             .on_hover_text("Add $1000 to cash")
             .clicked()
           {
-            game.cash += 1000;
+            game.cash += Money::new(1000);
           }
           if ui
             .add(Button::new("💵 -1k"))
             .on_hover_text("Remove $1000 from cash")
             .clicked()
           {
-            game.cash = game.cash.saturating_sub(1000);
+            game.cash -= Money::new(1000);
           }
           ui.separator();
           // MARK: DEV drug manipulation
@@ -197,7 +838,9 @@ fn render_dev_window(game: &mut Game, ctx: &egui::Context) {
             ))
             .clicked()
           {
-            game.inventory.add(drugs[game.selected_drug_idx], 10, 0);
+            game
+              .inventory
+              .add(drugs[game.selected_drug_idx], 10, Money::ZERO);
           }
           if ui
             .add(Button::new("-10"))
@@ -224,8 +867,8 @@ fn render_dev_window(game: &mut Game, ctx: &egui::Context) {
             .clicked()
           {
             game.last_prices = game.prices;
-            game.prices = get_rand_prices();
-            game.event = Some(events::Event::drug_bust(&mut game.prices));
+            game.prices = get_rand_prices(&game.ruleset.price_ranges, &mut game.rng);
+            game.event = Some(events::Event::drug_bust(&mut game.prices, &mut game.rng));
           }
           if ui
             .add(Button::new("🚢Shipment"))
@@ -233,15 +876,24 @@ fn render_dev_window(game: &mut Game, ctx: &egui::Context) {
             .clicked()
           {
             game.last_prices = game.prices;
-            game.prices = get_rand_prices();
-            game.event = Some(events::Event::drug_shipment(&mut game.prices));
+            game.prices = get_rand_prices(&game.ruleset.price_ranges, &mut game.rng);
+            game.event = Some(events::Event::drug_shipment(
+              &mut game.prices,
+              &mut game.rng,
+            ));
           }
           if ui
             .add(Button::new("🔪Mugging"))
             .on_hover_text("Trigger Mugging event")
             .clicked()
           {
-            game.event = Some(events::Event::mugging(&mut game.inventory, &mut game.cash));
+            game.event = Some(events::Event::mugging(
+              &mut game.inventory,
+              &mut game.cash,
+              &game.equipment,
+              game.ruleset.mugging_cash_ratio,
+              &mut game.rng,
+            ));
           }
         });
       });
@@ -267,24 +919,26 @@ pub fn main_panel(game: &mut Game, ctx: &egui::Context) {
 
 // MARK: render_debt_repayment()
 fn render_debt_repayment(game: &mut Game, ui: &mut egui::Ui) {
-  ui.add_enabled_ui(game.debt != 0, |ui| {
+  let repay = ui.add_enabled_ui(!game.debt.is_zero(), |ui| {
     ui.horizontal(|ui| {
-      let max_repay = if game.debt > 0 {
-        game.debt.min(game.cash)
+      let max_repay = if game.debt.is_zero() {
+        Money::new(1)
       } else {
-        1
+        game.debt.min(game.cash)
       };
       ui.add(
-        egui::Slider::new(&mut game.repay_amt, 0..=max_repay)
+        egui::Slider::new(&mut game.repay_amt, Money::ZERO..=max_repay)
           .trailing_fill(true)
           .prefix("$")
           .drag_value_speed(0.3),
       );
       if ui.button("Repay").clicked() {
-        game.repay_debt(game.repay_amt);
+        let result = game.repay_debt(game.repay_amt);
+        report_trade(game, result);
       }
     });
   });
+  highlight(game, ui, Highlight::Repay, repay.response.rect);
 }
 
 // MARK: render_stats_header()
@@ -292,24 +946,195 @@ fn render_stats_header(game: &mut Game, ui: &mut egui::Ui) {
   ui.horizontal(|ui| {
     ui.with_layout(Layout::left_to_right(Align::TOP), |ui| {
       ui.label(format!("Location: {}", game.location));
+      if let Some(run) = &game.scenario {
+        ui.separator();
+        ui.label(format!("Goal: {}", run.progress(game)))
+          .on_hover_text(run.scenario.goal.to_string());
+      }
     });
     ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
-      ui.label(format!("Days Left: {}", game.days_left));
+      match game.days_left {
+        Some(days) => ui.label(format!("Days Left: {}", days)),
+        None => ui.label(format!("Day: {}", game.day + 1)),
+      };
     });
   });
 
   ui.horizontal(|ui| {
     ui.with_layout(Layout::left_to_right(Align::TOP), |ui| {
-      ui.label(format!("Cash: ${}", game.cash.separate_with_commas()));
+      ui.label(format!(
+        "Space: {}/{}",
+        game.inventory.total_amount(),
+        game.equipment.capacity()
+      ));
+      ui.separator();
+      let heat_color = match game.heat.get() {
+        0..34 => Color32::GRAY,
+        34..67 => Color32::YELLOW,
+        _ => Color32::LIGHT_RED,
+      };
+      ui.colored_label(heat_color, format!("Heat: {}%", game.heat.get()))
+        .on_hover_text("Big trades, heavy pockets and fighting cops draw police attention.");
+    });
+    ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
+      if ui.button("Equipment").clicked() {
+        game.show_equipment = !game.show_equipment;
+      }
+      if ui.button("Rumours").clicked() {
+        game.show_rumours = !game.show_rumours;
+      }
+    });
+  });
+
+  let money = ui.horizontal(|ui| {
+    ui.with_layout(Layout::left_to_right(Align::TOP), |ui| {
+      ui.label(format!("Cash: {}", game.cash));
     });
     ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
-      ui.label(format!("Debt: ${}", game.debt.separate_with_commas()));
+      ui.label(format!("Debt: {}", game.debt));
     });
   });
+  highlight(game, ui, Highlight::Money, money.response.rect);
+
+  ui.horizontal(|ui| {
+    let worth = game.net_worth(Valuation::Current).total();
+    let average = game.net_worth(Valuation::Average).total();
+    ui.label(format!("Net Worth: {}", format_money(worth)))
+      .on_hover_text(format!(
+        "Cash, product and equipment, minus debt.\nAt average prices: {}",
+        format_money(average)
+      ));
+  });
 
   render_debt_repayment(game, ui);
 }
 
+// MARK: render_equipment_window()
+fn render_equipment_window(game: &mut Game, ctx: &egui::Context) {
+  let mut open = game.show_equipment;
+  egui::Window::new("Equipment")
+    .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+    .collapsible(false)
+    .resizable(false)
+    .open(&mut open)
+    .show(ctx, |ui| {
+      egui_extras::TableBuilder::new(ui)
+        .striped(true)
+        .columns(Column::auto(), 4)
+        .header(12.0, |mut header| {
+          header.col(|ui| {
+            ui.label("Item");
+          });
+          header.col(|ui| {
+            ui.label("Price");
+          });
+          header.col(|ui| {
+            ui.label("Owned");
+          });
+          header.col(|ui| {
+            ui.label("");
+          });
+        })
+        .body(|mut body| {
+          for item in get_equipment_list() {
+            body.row(18.0, |mut row| {
+              row.col(|ui| {
+                ui.label(item.to_string()).on_hover_text(item.description());
+              });
+              row.col(|ui| {
+                ui.label(item.price().to_string());
+              });
+              row.col(|ui| {
+                ui.label(game.equipment.get_amount(item).to_string());
+              });
+              row.col(|ui| {
+                if ui
+                  .add_enabled(game.cash >= item.price(), Button::new("Buy"))
+                  .on_disabled_hover_text("You can't afford it.")
+                  .clicked()
+                {
+                  game.buy_equipment(item);
+                }
+                if ui
+                  .add_enabled(game.equipment.get_amount(item) > 0, Button::new("Sell"))
+                  .on_hover_text(format!("Sell for {}", item.resale_price()))
+                  .clicked()
+                  && let Err(err) = game.sell_equipment(item)
+                {
+                  game.toasts.push(Toast::new(format!("⚠ {}", err)));
+                }
+              });
+            });
+          }
+        });
+    });
+  game.show_equipment = open;
+}
+
+// MARK: render_rumours_window()
+fn render_rumours_window(game: &mut Game, ctx: &egui::Context) {
+  let mut open = game.show_rumours;
+  egui::Window::new("Rumours")
+    .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+    .collapsible(false)
+    .resizable(false)
+    .open(&mut open)
+    .show(ctx, |ui| {
+      if game.rumours.heard().is_empty() {
+        ui.label("You haven't heard anything lately.");
+      }
+      for rumour in game.rumours.heard() {
+        ui.label(rumour.describe(game.day));
+      }
+      ui.separator();
+      if ui
+        .add_enabled(
+          game.cash >= INFORMANT_FEE,
+          Button::new(format!("Pay an informant ({})", INFORMANT_FEE)),
+        )
+        .on_hover_text("Informants are right far more often than street talk.")
+        .on_disabled_hover_text("You can't afford it.")
+        .clicked()
+      {
+        game.pay_informant();
+      }
+    });
+  game.show_rumours = open;
+}
+
+// MARK: render_autopilot_window()
+fn render_autopilot_window(game: &mut Game, ctx: &egui::Context) {
+  let mut open = game.show_autopilot;
+  egui::Window::new("Autopilot")
+    .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+    .collapsible(false)
+    .resizable(false)
+    .open(&mut open)
+    .show(ctx, |ui| {
+      ui.label("A bot takes over: it sells at a profit, pays down debt,");
+      ui.label("buys on the advisor's best tip and travels to cash it in.");
+      egui::Grid::new("autopilot_settings").show(ui, |ui| {
+        ui.label("Days");
+        ui.add(egui::DragValue::new(&mut game.autopilot.days).range(1..=MAX_AUTOPILOT_DAYS));
+        ui.end_row();
+        ui.label("Speed");
+        ui.add(
+          egui::Slider::new(
+            &mut game.autopilot.speed,
+            MIN_AUTOPILOT_SPEED..=MAX_AUTOPILOT_SPEED,
+          )
+          .suffix(" actions/s"),
+        );
+        ui.end_row();
+      });
+      if ui.button("Start").clicked() {
+        game.autopilot.start();
+        game.show_autopilot = false;
+      }
+    });
+  game.show_autopilot &= open;
+}
+
 // MARK: render_inventory_table()
 fn render_inventory_table(game: &mut Game, ui: &mut egui::Ui) {
   egui_extras::TableBuilder::new(ui)
@@ -329,7 +1154,7 @@ fn render_inventory_table(game: &mut Game, ui: &mut egui::Ui) {
     .body(|mut body| {
       for drug in get_drug_list() {
         let amt = game.inventory.get_amount(drug).unwrap_or(0);
-        let cost = game.inventory.get_cost(drug).unwrap_or(0);
+        let cost = game.inventory.get_cost(drug).unwrap_or_default();
         body.row(18.0, |mut row| {
           row.col(|ui| {
             ui.label(drug.to_string());
@@ -349,20 +1174,14 @@ fn render_inventory_table(game: &mut Game, ui: &mut egui::Ui) {
 pub fn right_panel(game: &mut Game, ctx: &egui::Context) {
   egui::CentralPanel::default().show(ctx, |ui| {
     // MARK: travel section
+    let top = ui.cursor().min;
     egui_extras::TableBuilder::new(ui)
       .columns(Column::remainder(), 3)
       .body(|mut body| {
         for t_row in 0..2 {
           body.row(16.0, |mut row| {
             for t_cell in 0..3 {
-              let loc = match t_row * 3 + t_cell {
-                1 => Location::Oakwood,
-                2 => Location::Lakeview,
-                3 => Location::Highland,
-                4 => Location::Edgewater,
-                5 => Location::Centerville,
-                _ => Location::Fairfield, // 0 or > 5
-              };
+              let loc = get_location_list()[t_row * 3 + t_cell];
               row.col(|ui| {
                 if ui
                   .add_enabled(
@@ -379,6 +1198,50 @@ pub fn right_panel(game: &mut Game, ctx: &egui::Context) {
           });
         }
       });
+    highlight(game, ui, Highlight::Travel, region_since(ui, top));
+    ui.horizontal(|ui| {
+      if ui
+        .button("Wait a day")
+        .on_hover_text("Stay put and let the market move.")
+        .clicked()
+      {
+        game.wait();
+      }
+      if game.game_length == GameLength::Endless
+        && ui
+          .button("Retire")
+          .on_hover_text("Cash out at today's prices and end the game.")
+          .clicked()
+      {
+        game.retire();
+      }
+      if game.autopilot.is_running() {
+        if ui
+          .button(format!(
+            "⏹ Stop Autopilot ({}d)",
+            game.autopilot.days_left()
+          ))
+          .clicked()
+        {
+          game.autopilot.stop();
+        }
+      } else if game.ruleset.advisor_enabled
+        && ui
+          .button("🤖 Autopilot")
+          .on_hover_text("Let a bot play for a few days.")
+          .clicked()
+      {
+        game.show_autopilot = !game.show_autopilot;
+      }
+      if let Some(receipts) = game.undo.last()
+        && ui
+          .button("↩ Undo")
+          .on_hover_text(format!("Undo: {} (Ctrl+Z)", describe_receipts(&receipts)))
+          .clicked()
+      {
+        undo_trade(game);
+      }
+    });
     ui.separator();
     // MARK: trading section
     ui.with_layout(
@@ -393,6 +1256,7 @@ pub fn right_panel(game: &mut Game, ctx: &egui::Context) {
 
 // MARK: render_drug_trading_table()
 fn render_drug_trading_table(game: &mut Game, ui: &mut egui::Ui) {
+  let top = ui.cursor().min;
   egui_extras::TableBuilder::new(ui)
     .striped(true)
     .columns(Column::auto(), 4)
@@ -413,14 +1277,12 @@ fn render_drug_trading_table(game: &mut Game, ui: &mut egui::Ui) {
     .body(|mut body| {
       for drug in get_drug_list() {
         body.row(18.0, |mut row| {
-          let col = if game.event.is_some() {
-            match game.event.as_ref().unwrap().e_type {
-              events::EventType::DrugBust => Color32::LIGHT_RED,
-              events::EventType::DrugShipment => Color32::LIGHT_GREEN,
-              _ => Color32::GRAY,
+          let col = match game.event.as_ref().map(|event| &event.e_type) {
+            Some(events::EventType::DrugBust | events::EventType::WarehouseRaid) => {
+              Color32::LIGHT_RED
             }
-          } else {
-            Color32::GRAY
+            Some(events::EventType::DrugShipment) => Color32::LIGHT_GREEN,
+            _ => Color32::GRAY,
           };
           let ico = match game.prices[drug as usize] {
             price if price > game.last_prices[drug as usize] => icons::ICON_TRENDING_UP,
@@ -437,23 +1299,55 @@ fn render_drug_trading_table(game: &mut Game, ui: &mut egui::Ui) {
               if game.event.is_some() && game.event.as_ref().unwrap().e_drugs.contains(&drug) {
                 ui.visuals_mut().override_text_color = Some(col);
               }
-              ui.label(ico);
-              ui.label(format!(" ${}", game.prices[drug as usize]));
+              let price_label =
+                ui.label(ico) | ui.label(format!(" ${}", game.prices[drug as usize]));
+              if let Some(shock) = game.market.shock_at(game.location, drug) {
+                let change = ((shock.multiplier - 1.0) * 100.0).round() as i32;
+                price_label.on_hover_text(format!(
+                  "{} prices are {}{}% here for {} more day(s).",
+                  drug,
+                  if change > 0 { "+" } else { "" },
+                  change,
+                  shock.days_left
+                ));
+              }
               ui.reset_style();
             });
           });
           // MARK: buy section
           row.col(|ui| {
             ui.horizontal(|ui| {
-              let max_buy = game.cash / game.prices[drug as usize];
+              let max_buy = game.max_buy(drug);
               egui::DragValue::new(&mut game.buy_amts[drug as usize])
                 .range(0..=max_buy)
                 // .speed(0.1)
                 .ui(ui);
-              if ui.button("Buy").clicked()
-                && game.cash >= game.prices[drug as usize] * game.buy_amts[drug as usize]
+              if ui.button("Buy").clicked() {
+                let result = game.buy(drug, game.buy_amts[drug as usize]);
+                report_trade(game, result);
+                game.buy_amts[drug as usize] = 0;
+              }
+              if ui
+                .button("Max")
+                .on_hover_text(format!(
+                  "Buy as many as you can: {} ({})",
+                  max_buy,
+                  drug.as_index() + 1
+                ))
+                .clicked()
               {
-                game.buy(drug, game.buy_amts[drug as usize]);
+                let result = game.buy_max(drug);
+                report_trade(game, result);
+                game.buy_amts[drug as usize] = 0;
+              }
+              if ui
+                .add_enabled(game.buy_amts[drug as usize] > 0, egui::Button::new("🛒"))
+                .on_hover_text("Add to basket")
+                .clicked()
+              {
+                game
+                  .basket
+                  .add(Order::Buy(drug, game.buy_amts[drug as usize]));
                 game.buy_amts[drug as usize] = 0;
               }
             });
@@ -467,15 +1361,147 @@ fn render_drug_trading_table(game: &mut Game, ui: &mut egui::Ui) {
                 // .speed(0.1)
                 .ui(ui);
               if ui.button("Sell").clicked() {
-                let amt = game.inventory.get_amount(drug).unwrap_or(0);
-                if amt >= game.sell_amts[drug as usize] {
-                  game.sell(drug, game.sell_amts[drug as usize]);
-                  game.sell_amts[drug as usize] = 0;
-                }
+                let result = game.sell(drug, game.sell_amts[drug as usize]);
+                report_trade(game, result);
+                game.sell_amts[drug as usize] = 0;
+              }
+              if ui
+                .button("All")
+                .on_hover_text(format!("Sell all {} (Shift+{})", drug, drug.as_index() + 1))
+                .clicked()
+              {
+                let result = game.sell_all(drug);
+                report_trade(game, result);
+                game.sell_amts[drug as usize] = 0;
+              }
+              if ui
+                .add_enabled(game.sell_amts[drug as usize] > 0, egui::Button::new("🛒"))
+                .on_hover_text("Add to basket")
+                .clicked()
+              {
+                game
+                  .basket
+                  .add(Order::Sell(drug, game.sell_amts[drug as usize]));
+                game.sell_amts[drug as usize] = 0;
               }
             });
           });
         });
       }
     });
+  ui.horizontal(|ui| {
+    if ui
+      .button("Sell Everything")
+      .on_hover_text("Sell everything you're carrying (L)")
+      .clicked()
+    {
+      let result = game.sell_everything();
+      report_bulk_sale(game, result);
+    }
+    if ui
+      .button("Sell at a Profit")
+      .on_hover_text("Sell whatever goes for more than you paid (P)")
+      .clicked()
+    {
+      let result = game.sell_profitable();
+      report_bulk_sale(game, result);
+    }
+  });
+  render_advisor(game, ui);
+  render_basket(game, ui);
+  highlight(game, ui, Highlight::Trading, region_since(ui, top));
+}
+
+// MARK: render_advisor()
+fn render_advisor(game: &Game, ui: &mut egui::Ui) {
+  if !game.ruleset.advisor_enabled {
+    return;
+  }
+  let Some(tip) = game.advisor.suggest(game) else {
+    ui.weak("💡 Visit more places and the advisor will suggest trades.");
+    return;
+  };
+  let risk_color = match tip.risk {
+    Risk::Low => Color32::GRAY,
+    Risk::Medium => Color32::YELLOW,
+    Risk::High => Color32::LIGHT_RED,
+  };
+  ui.horizontal_wrapped(|ui| {
+    ui.label(format!(
+      "💡 Buy {} here at ${}, sell in {} for ${} (+{}%)",
+      tip.drug,
+      tip.buy_price,
+      tip.sell_at,
+      tip.sell_price,
+      tip.margin_percent()
+    ))
+    .on_hover_text(format!(
+      "{} expected on {} units. Price seen {} day(s) ago.",
+      tip.expected_profit(),
+      tip.amount,
+      tip.age
+    ));
+    ui.colored_label(risk_color, format!("Risk: {}", tip.risk));
+  });
+}
+
+// MARK: render_basket()
+fn render_basket(game: &mut Game, ui: &mut egui::Ui) {
+  if game.basket.is_empty() {
+    return;
+  }
+  ui.separator();
+  ui.label("Basket");
+  let mut removed = None;
+  for order in game.basket.orders() {
+    ui.horizontal(|ui| {
+      if ui.small_button("✖").on_hover_text("Remove").clicked() {
+        removed = Some(order);
+      }
+      ui.label(order.to_string());
+    });
+  }
+  if let Some(order) = removed {
+    game.basket.remove(order);
+  }
+  let preview = game.basket.preview(game);
+  match preview {
+    Ok(preview) => {
+      ui.label(format!(
+        "Afterwards: {} cash, {} held, {} space left",
+        preview.cash,
+        preview.holdings.iter().sum::<u32>(),
+        preview.free_space
+      ));
+    }
+    Err(err) => {
+      ui.colored_label(Color32::LIGHT_RED, format!("⚠ {}", err));
+    }
+  }
+  ui.horizontal(|ui| {
+    if ui
+      .add_enabled(preview.is_ok(), egui::Button::new("Execute"))
+      .on_hover_text("Make every trade in the basket, or none of them")
+      .clicked()
+    {
+      let text = match game.execute_basket() {
+        Ok(receipts) => format!(
+          "🛒 Made {} trade(s), {} cash left",
+          receipts.len(),
+          game.cash
+        ),
+        Err(err) => format!("⚠ {}", err),
+      };
+      game.toasts.push(Toast::new(text));
+    }
+    if ui.button("Clear").clicked() {
+      game.basket.clear();
+    }
+  });
+}
+
+// MARK: region_since()
+/// The area of `ui` filled since the cursor was at `top`.
+fn region_since(ui: &egui::Ui, top: egui::Pos2) -> egui::Rect {
+  egui::Rect::from_min_max(top, egui::pos2(ui.min_rect().right(), ui.cursor().top()))
 }
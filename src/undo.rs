@@ -0,0 +1,133 @@
+use crate::drugs::Drug;
+use crate::game::Game;
+use crate::heat::Heat;
+use crate::money::Money;
+use crate::trade::TradeReceipt;
+
+// MARK: UndoEntry struct
+/// A completed trade, and everything it changed as it was before.
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+  pub receipt: TradeReceipt,
+  cash: Money,
+  debt: Money,
+  heat: Heat,
+  holding: Option<(Drug, Option<(u32, Money)>)>,
+}
+
+impl UndoEntry {
+  // MARK: UndoEntry::capture()
+  /// Remembers the state a trade is about to change; `drug` is the one being traded, if any.
+  pub fn capture(game: &Game, drug: Option<Drug>, receipt: TradeReceipt) -> Self {
+    Self {
+      receipt,
+      cash: game.cash,
+      debt: game.debt,
+      heat: game.heat,
+      holding: drug.map(|drug| (drug, game.inventory.holding(drug))),
+    }
+  }
+
+  // MARK: restore()
+  pub fn restore(self, game: &mut Game) -> TradeReceipt {
+    game.cash = self.cash;
+    game.debt = self.debt;
+    game.heat = self.heat;
+    if let Some((drug, holding)) = self.holding {
+      game.inventory.restore(drug, holding);
+    }
+    self.receipt
+  }
+}
+
+// MARK: - UndoStack struct
+/// Trades made since the day started, newest last, in steps that are undone
+/// whole: usually a single trade, or every order of an executed basket.
+#[derive(Debug, Default, Clone)]
+pub struct UndoStack {
+  steps: Vec<Vec<UndoEntry>>,
+}
+
+impl UndoStack {
+  // MARK: push()
+  pub fn push(&mut self, entry: UndoEntry) {
+    self.steps.push(vec![entry]);
+  }
+
+  // MARK: join()
+  /// Makes every step pushed since `len()` was `mark` into one.
+  pub fn join(&mut self, mark: usize) {
+    if mark + 1 >= self.steps.len() {
+      return;
+    }
+    let joined = self.steps.drain(mark..).flatten().collect();
+    self.steps.push(joined);
+  }
+
+  // MARK: pop()
+  /// The latest step's trades, oldest first.
+  pub fn pop(&mut self) -> Option<Vec<UndoEntry>> {
+    self.steps.pop()
+  }
+
+  // MARK: clear()
+  pub fn clear(&mut self) {
+    self.steps.clear();
+  }
+
+  // MARK: len()
+  pub fn len(&self) -> usize {
+    self.steps.len()
+  }
+
+  // MARK: last()
+  /// What undoing the latest step would take back.
+  pub fn last(&self) -> Option<Vec<TradeReceipt>> {
+    let step = self.steps.last()?;
+    Some(step.iter().map(|entry| entry.receipt).collect())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::drugs::Drug;
+  use crate::game::Game;
+
+  fn started() -> Game {
+    let mut game = Game::with_seed(3);
+    game.start();
+    game
+  }
+
+  #[test]
+  fn undoing_a_buy_restores_cash_amount_and_cost() {
+    let mut game = started();
+    game.buy(Drug::Weed, 2).unwrap();
+    let (cash, heat, holding) = (game.cash, game.heat, game.inventory.holding(Drug::Weed));
+    game.buy(Drug::Weed, 3).unwrap();
+    assert!(game.undo().is_some());
+    assert_eq!(game.cash, cash);
+    assert_eq!(game.heat, heat);
+    assert_eq!(game.inventory.holding(Drug::Weed), holding);
+  }
+
+  #[test]
+  fn undoing_a_sale_brings_back_an_emptied_holding() {
+    let mut game = started();
+    game.buy(Drug::Weed, 4).unwrap();
+    let (cash, holding) = (game.cash, game.inventory.holding(Drug::Weed));
+    game.sell(Drug::Weed, 4).unwrap();
+    assert_eq!(game.inventory.get_amount(Drug::Weed).unwrap_or(0), 0);
+    assert!(game.undo().is_some());
+    assert_eq!(game.cash, cash);
+    assert_eq!(game.inventory.holding(Drug::Weed), holding);
+  }
+
+  #[test]
+  fn trades_stop_being_undoable_once_the_day_ends() {
+    let mut game = started();
+    game.buy(Drug::Weed, 1).unwrap();
+    game.wait();
+    assert!(game.undo().is_none());
+  }
+}